@@ -1,20 +1,26 @@
 #![feature(let_chains)]
 
 use std::{
-    collections::BTreeMap,
     sync::{atomic::AtomicBool, Arc},
     time::Duration,
 };
 
 use anyhow::Ok;
+use arc_swap::ArcSwap;
 use parking_lot::Mutex;
 
 use poise::serenity_prelude as serenity;
-use serenity::small_fixed_array::FixedString;
+use small_fixed_array::FixedString;
 
 use tts_core::{
-    analytics, create_db_handler, database,
-    structs::{Data, PollyVoice, RegexCache, Result, TTSMode},
+    analytics,
+    circuit_breaker::CircuitBreakers,
+    constants::{DEFAULT_TTS_CONNECT_TIMEOUT, DEFAULT_TTS_READ_TIMEOUT},
+    create_db_handler, database,
+    normalization::Normalizers,
+    structs::{Data, LastToXsaidTracker, QueuedMessages, RegexCache, Result},
+    translations::Translations,
+    voices::VoiceLists,
 };
 use tts_tasks::Looper as _;
 
@@ -35,10 +41,27 @@ fn main() -> Result<()> {
 
 async fn main_(start_time: std::time::SystemTime) -> Result<()> {
     println!("Loading and performing migrations");
-    let (pool, config) = tts_migrations::load_db_and_conf().await?;
+    let (pool, mut config) = tts_migrations::load_db_and_conf().await?;
+    if let Some(premium_config) = &mut config.premium {
+        premium_config.load_legacy_skus()?;
+    }
 
     println!("Initialising Http client");
+    let connect_timeout = config.main.tts_service_connect_timeout_secs;
+    let read_timeout = config.main.tts_service_read_timeout_secs;
     let reqwest = reqwest::Client::new();
+    let tts_reqwest = reqwest::Client::builder()
+        .connect_timeout(
+            connect_timeout
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TTS_CONNECT_TIMEOUT),
+        )
+        .read_timeout(
+            read_timeout
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TTS_READ_TIMEOUT),
+        )
+        .build()?;
     let auth_key = config.main.tts_service_auth_key.as_deref();
 
     let token = config.main.token.clone();
@@ -53,7 +76,6 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
     let http = Arc::new(http_builder.build());
 
     println!("Performing big startup join");
-    let tts_service = || config.main.tts_service.clone();
     let (
         webhooks,
         guilds_db,
@@ -61,11 +83,8 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         user_voice_db,
         guild_voice_db,
         nickname_db,
-        gtts_voices,
-        espeak_voices,
-        gcloud_voices,
-        polly_voices,
-        translation_languages,
+        voice_announcements_db,
+        voices,
         premium_user,
     ) = tokio::try_join!(
         get_webhooks(&http, config.webhooks),
@@ -74,11 +93,25 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         create_db_handler!(pool.clone(), "user_voice", "user_id", "mode"),
         create_db_handler!(pool.clone(), "guild_voice", "guild_id", "mode"),
         create_db_handler!(pool.clone(), "nicknames", "guild_id", "user_id"),
-        fetch_voices(&reqwest, tts_service(), auth_key, TTSMode::gTTS),
-        fetch_voices(&reqwest, tts_service(), auth_key, TTSMode::eSpeak),
-        fetch_voices(&reqwest, tts_service(), auth_key, TTSMode::gCloud),
-        fetch_voices::<Vec<PollyVoice>>(&reqwest, tts_service(), auth_key, TTSMode::Polly),
-        fetch_translation_languages(&reqwest, tts_service(), auth_key),
+        create_db_handler!(pool.clone(), "voice_announcements", "guild_id"),
+        async {
+            let res = VoiceLists::fetch(&tts_reqwest, &config.main.tts_service, auth_key).await;
+
+            // With a local synthesizer, the bot can run without the tts-service until a voice reload succeeds.
+            #[cfg(feature = "local-tts")]
+            let res = match res {
+                Err(err) if config.local_tts.is_some() => {
+                    println!("Failed to load voices, starting with only local TTS: {err:?}");
+                    Ok(VoiceLists::empty())
+                }
+                res => res,
+            };
+
+            let res = res?;
+
+            println!("Loaded voices and translation languages");
+            Ok(res)
+        },
         async {
             let res = serenity::UserId::new(802632257658683442)
                 .to_user(&http)
@@ -99,10 +132,19 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
     println!("Sending startup message");
     let startup_message = send_startup_message(&http, &webhooks.logs).await?;
 
+    println!("Loading translations");
+    let translations = Translations::load("translations")?;
+    let mut commands = tts_commands::commands();
+    translations.localize_commands(&mut commands);
+
+    println!("Loading text normalization rules");
+    let normalizers = Normalizers::load("normalization")?;
+
     println!("Spawning analytics handler");
     let analytics = Arc::new(analytics::Handler::new(pool.clone()));
     tokio::spawn(analytics.clone().start());
 
+    let dashboard_config = config.dashboard;
     let data = Arc::new(Data {
         pool,
         system_info: Mutex::new(sysinfo::System::new()),
@@ -111,40 +153,49 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         fully_started: AtomicBool::new(false),
         join_vc_tokens: dashmap::DashMap::new(),
         songbird: songbird::Songbird::serenity(),
-        last_to_xsaid_tracker: dashmap::DashMap::new(),
+        last_to_xsaid_tracker: LastToXsaidTracker::default(),
+        last_voice_activity: dashmap::DashMap::new(),
         update_startup_lock: tokio::sync::Mutex::new(()),
         entitlement_cache: mini_moka::sync::Cache::builder()
             .time_to_live(Duration::from_secs(60 * 60))
             .build(),
+        voice_announcement_cooldowns: mini_moka::sync::Cache::builder()
+            .time_to_live(Duration::from_secs(30))
+            .build(),
+        link_names_cache: mini_moka::sync::Cache::builder()
+            .time_to_live(Duration::from_secs(60 * 10))
+            .build(),
+        queued_messages: QueuedMessages::default(),
 
-        gtts_voices,
-        espeak_voices,
-        translation_languages,
-        gcloud_voices: prepare_gcloud_voices(gcloud_voices),
-        polly_voices: polly_voices
-            .into_iter()
-            .map(|v| (v.id.clone(), v))
-            .collect::<BTreeMap<_, _>>(),
+        voices: ArcSwap::from_pointee(voices),
+        circuit_breakers: CircuitBreakers::default(),
+        #[cfg(feature = "local-tts")]
+        local_tts: config.local_tts,
 
         config: config.main,
         premium_config: config.premium,
         website_info: Mutex::new(config.website_info),
         reqwest,
+        tts_reqwest,
         premium_avatar_url: FixedString::from_string_trunc(premium_user.face()),
         analytics,
         webhooks,
         start_time,
         startup_message,
         regex_cache: RegexCache::new()?,
+        translations,
+        normalizers,
         guilds_db,
         userinfo_db,
         nickname_db,
         user_voice_db,
         guild_voice_db,
+        voice_announcements_db,
     });
 
+    let dashboard_data = data.clone();
     let framework_options = poise::FrameworkOptions {
-        commands: tts_commands::commands(),
+        commands,
         event_handler: |fw_ctx, event| Box::pin(tts_events::listen(fw_ctx, event)),
         on_error: |error| {
             Box::pin(async move {
@@ -172,6 +223,16 @@ async fn main_(start_time: std::time::SystemTime) -> Result<()> {
         .data(data as _)
         .await?;
 
+    if let Some(dashboard_config) = dashboard_config {
+        println!("Starting dashboard API");
+        let cache = client.cache.clone();
+        tokio::spawn(async move {
+            if let Err(err) = tts_dashboard::serve(dashboard_data, cache, dashboard_config).await {
+                tracing::error!("Dashboard API stopped: {err:?}");
+            }
+        });
+    }
+
     let shard_manager = client.shard_manager.clone();
 
     tokio::spawn(async move {
@@ -0,0 +1,210 @@
+use std::fmt::Write as _;
+
+use poise::{
+    serenity_prelude::{self as serenity, builder::*},
+    CreateReply,
+};
+
+use tts_core::{
+    common::{next_cron_run, parse_cron},
+    database_models::AnnouncementRow,
+    opt_ext::OptionTryUnwrap as _,
+    structs::{Command, CommandResult, Context},
+    traits::PoiseContextExt as _,
+};
+
+const MAX_ANNOUNCEMENTS: i64 = 25;
+
+/// Schedules messages to be said in voice at a given time
+#[poise::command(
+    category = "Settings",
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES | EMBED_LINKS",
+    subcommands("schedule", "list", "remove")
+)]
+pub async fn announce(ctx: Context<'_>) -> CommandResult {
+    super::help::command(ctx, Some("announce")).await
+}
+
+/// Schedules a one-off or repeating message to be said in voice
+#[poise::command(
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES"
+)]
+pub async fn schedule(
+    ctx: Context<'_>,
+    #[description = "The message to say"] message: String,
+    #[description = "When to say it once, in UTC as YYYY-MM-DD HH:MM"] at: Option<String>,
+    #[description = "A cron schedule to repeat on, in UTC"] repeat: Option<String>,
+    #[description = "The voice channel to join if not already connected"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let now = chrono::Utc::now().timestamp();
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    let (schedule, next_run) = match (at, repeat) {
+        (Some(at), None) => {
+            let Ok(at) = chrono::NaiveDateTime::parse_from_str(&at, "%Y-%m-%d %H:%M") else {
+                ctx.send_error(t("announce.invalid_time")).await?;
+                return Ok(());
+            };
+
+            let next_run = at.and_utc().timestamp();
+            if next_run <= now {
+                ctx.send_error(t("announce.past_time")).await?;
+                return Ok(());
+            }
+
+            (None, next_run)
+        }
+        (None, Some(repeat)) => {
+            let next_run = parse_cron(&repeat)
+                .ok()
+                .and_then(|schedule| next_cron_run(&schedule, now));
+
+            let Some(next_run) = next_run else {
+                ctx.send_error(t("announce.invalid_repeat")).await?;
+                return Ok(());
+            };
+
+            (Some(repeat), next_run)
+        }
+        _ => {
+            ctx.send_error(t("announce.at_or_repeat")).await?;
+            return Ok(());
+        }
+    };
+
+    let (existing,): (i64,) =
+        sqlx::query_as("SELECT count(*) FROM announcements WHERE guild_id = $1")
+            .bind(guild_id.get() as i64)
+            .fetch_one(&data.pool)
+            .await?;
+
+    if existing >= MAX_ANNOUNCEMENTS {
+        let max_announcements = MAX_ANNOUNCEMENTS.to_string();
+        let msg = t("announce.too_many").replace("{max_announcements}", &max_announcements);
+        ctx.send_error(msg).await?;
+        return Ok(());
+    }
+
+    data.guilds_db.create_row(guild_id.into()).await?;
+    let (id,): (i64,) = sqlx::query_as(
+        "
+        INSERT INTO announcements(guild_id, channel_id, message, schedule, next_run)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+    ",
+    )
+    .bind(guild_id.get() as i64)
+    .bind(channel.map(|c| c.id.get() as i64))
+    .bind(message)
+    .bind(schedule)
+    .bind(next_run)
+    .fetch_one(&data.pool)
+    .await?;
+
+    let msg = t("announce.scheduled")
+        .replace("{id}", &id.to_string())
+        .replace("{next_run}", &next_run.to_string());
+
+    ctx.say(msg).await?;
+    Ok(())
+}
+
+/// Lists the announcements scheduled in this server
+#[poise::command(
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES | EMBED_LINKS"
+)]
+pub async fn list(ctx: Context<'_>) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let announcements: Vec<AnnouncementRow> =
+        sqlx::query_as("SELECT * FROM announcements WHERE guild_id = $1 ORDER BY next_run")
+            .bind(guild_id.get() as i64)
+            .fetch_all(&data.pool)
+            .await?;
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    if announcements.is_empty() {
+        ctx.say(t("announce.none")).await?;
+        return Ok(());
+    }
+
+    let mut description = String::new();
+    for announcement in announcements {
+        let repeat = announcement
+            .schedule
+            .as_deref()
+            .unwrap_or(t("announce.once"));
+        let message: String = announcement.message.chars().take(50).collect();
+
+        writeln!(
+            description,
+            "`{}`: <t:{}:f> (`{repeat}`) - {message}",
+            announcement.id, announcement.next_run
+        )?;
+    }
+
+    let embed = CreateEmbed::default()
+        .title(t("announce.list_title"))
+        .description(description)
+        .colour(ctx.neutral_colour().await);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Removes a scheduled announcement
+#[poise::command(
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES"
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The ID of the announcement, shown in /announce list"] id: i64,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().try_unwrap()?;
+    let result = sqlx::query("DELETE FROM announcements WHERE id = $1 AND guild_id = $2")
+        .bind(id)
+        .bind(guild_id.get() as i64)
+        .execute(&data.pool)
+        .await?;
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    if result.rows_affected() == 0 {
+        ctx.send_error(t("announce.not_found")).await?;
+    } else {
+        let msg = t("announce.removed").replace("{id}", &id.to_string());
+        ctx.say(msg).await?;
+    }
+
+    Ok(())
+}
+
+pub fn commands() -> [Command; 1] {
+    [announce()]
+}
@@ -9,10 +9,11 @@ use serenity::all::{self as serenity, Mentionable as _};
 use tts_core::{
     constants::PREMIUM_NEUTRAL_COLOUR,
     opt_ext::OptionTryUnwrap as _,
-    structs::{Command, Context, FailurePoint, PartialContext, Result},
+    structs::{Command, Context, FailurePoint, PartialContext, PremiumPerk, Result},
     traits::PoiseContextExt,
 };
 
+mod announce;
 mod help;
 mod main_;
 mod other;
@@ -27,11 +28,13 @@ pub fn commands() -> Vec<Command> {
         .chain(other::commands())
         .chain(settings::commands())
         .chain(premium::commands())
+        .chain(announce::commands())
         .chain(owner::commands())
         .chain(help::commands())
         .collect()
 }
 
+/// Checks the server is premium with a tier including [`PremiumPerk::Translation`].
 pub async fn premium_command_check(ctx: Context<'_>) -> Result<bool> {
     if let Context::Application(ctx) = ctx {
         if ctx.interaction_type == poise::CommandInteractionType::Autocomplete {
@@ -44,20 +47,22 @@ pub async fn premium_command_check(ctx: Context<'_>) -> Result<bool> {
     let guild_id = ctx.guild_id();
     let serenity_ctx = ctx.serenity_context();
 
-    let mut main_msg = match data.premium_check(ctx.http(), guild_id).await? {
-        None => return Ok(true),
-        Some(FailurePoint::Guild) => {
-            Cow::Borrowed("Hey, this is a premium command so it must be run in a server!")
-        }
-        Some(FailurePoint::PremiumUser) => Cow::Borrowed(
-            "Hey, this server isn't premium, please purchase TTS Bot Premium! (`/premium`)",
-        ),
-        Some(FailurePoint::NotSubscribed(premium_user_id)) => {
+    let failure_point = match data.premium_check(ctx.http(), guild_id).await? {
+        Ok(premium) if premium.has_perk(PremiumPerk::Translation) => return Ok(true),
+        Ok(_) => FailurePoint::MissingPerk(PremiumPerk::Translation),
+        Err(failure_point) => failure_point,
+    };
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    let mut main_msg = match failure_point {
+        FailurePoint::Guild => Cow::Borrowed(t("premium_check.guild")),
+        FailurePoint::PremiumUser => Cow::Borrowed(t("premium_check.not_premium")),
+        FailurePoint::MissingPerk(_) => Cow::Borrowed(t("premium_check.missing_perk")),
+        FailurePoint::NotSubscribed(premium_user_id) => {
             let premium_user = premium_user_id.to_user(serenity_ctx).await?;
-            Cow::Owned(format!(concat!(
-                "Hey, this server has a premium user setup, however they no longer have a subscription! ",
-                "Please ask {} to renew their membership."
-            ), premium_user.tag()))
+            Cow::Owned(t("premium_check.not_subscribed").replace("{user}", &premium_user.tag()))
         }
     };
 
@@ -78,20 +83,20 @@ pub async fn premium_command_check(ctx: Context<'_>) -> Result<bool> {
     if permissions.send_messages() {
         let builder = poise::CreateReply::default();
         ctx.send({
-            const FOOTER_MSG: &str = "If this is an error, please contact GnomedDev.";
+            let footer_msg = t("premium_check.footer");
             if permissions.embed_links() {
                 let embed = serenity::CreateEmbed::default()
-                    .title("TTS Bot Premium - Premium Only Command!")
+                    .title(t("premium_check.title"))
                     .description(main_msg)
                     .colour(PREMIUM_NEUTRAL_COLOUR)
                     .thumbnail(data.premium_avatar_url.as_str())
-                    .footer(serenity::CreateEmbedFooter::new(FOOTER_MSG));
+                    .footer(serenity::CreateEmbedFooter::new(footer_msg));
 
                 builder.embed(embed)
             } else {
                 let main_msg = main_msg.to_mut();
                 main_msg.push('\n');
-                main_msg.push_str(FOOTER_MSG);
+                main_msg.push_str(footer_msg);
                 builder.content(main_msg.as_str())
             }
         })
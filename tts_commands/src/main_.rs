@@ -6,7 +6,7 @@ use poise::serenity_prelude::{self as serenity, builder::*, colours::branding::Y
 use songbird::error::JoinError;
 
 use tts_core::{
-    common::{push_permission_names, random_footer},
+    common::{is_setup_channel, push_permission_names, random_footer, request_stage_speak},
     constants::RED,
     database_models::GuildRow,
     opt_ext::OptionTryUnwrap as _,
@@ -23,8 +23,13 @@ async fn channel_check(
     let guild_id = ctx.guild_id().unwrap();
     let guild_row = ctx.data().guilds_db.get(guild_id.into()).await?;
 
-    let channel_id = Some(ctx.channel_id());
-    if guild_row.channel == channel_id || author_vc == channel_id {
+    let channel_id = ctx.channel_id();
+    let in_setup_channel = {
+        let guild = require_guild!(ctx, Ok(None));
+        is_setup_channel(&guild, &guild_row, channel_id)
+    };
+
+    if in_setup_channel || author_vc == Some(channel_id) {
         return Ok(Some(guild_row));
     }
 
@@ -171,7 +176,8 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
             }
 
             tracing::warn!("Channel {bot_channel_id} didn't exist in {guild_id} in `/join`");
-            data.last_to_xsaid_tracker.remove(&guild_id);
+            data.last_to_xsaid_tracker.remove_guild(guild_id);
+            data.queued_messages.remove_guild(guild_id);
             data.songbird.remove(guild_id).await?;
         }
     }
@@ -192,6 +198,12 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
             };
         }
 
+        data.last_voice_activity
+            .insert(guild_id, std::time::Instant::now());
+
+        let (http, cache) = (ctx.http(), ctx.cache());
+        request_stage_speak(http, cache, &guild_row, guild_id, author_vc).await;
+
         match ctx {
             Context::Application(poise::ApplicationContext { interaction, .. }) => {
                 interaction.member.as_deref().try_unwrap()?.display_name()
@@ -209,6 +221,8 @@ pub async fn join(ctx: Context<'_>) -> CommandResult {
         .thumbnail(bot_face)
         .author(CreateEmbedAuthor::new(display_name).icon_url(ctx.author().face()))
         .footer(CreateEmbedFooter::new(random_footer(
+            &data.translations,
+            ctx.language().await?,
             &data.config.main_server_invite,
             bot_id,
         )));
@@ -269,7 +283,8 @@ pub async fn leave(ctx: Context<'_>) -> CommandResult {
             ctx.say("Error: You need to be in the same voice channel as me to make me leave!")
                 .await?;
         } else {
-            data.last_to_xsaid_tracker.remove(&guild_id);
+            data.last_to_xsaid_tracker.remove_guild(guild_id);
+            data.queued_messages.remove_guild(guild_id);
             data.songbird.remove(guild_id).await?;
 
             ctx.say("Left voice channel!").await?;
@@ -282,9 +297,23 @@ pub async fn leave(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
+async fn acknowledge(ctx: Context<'_>) -> Result<()> {
+    match ctx {
+        poise::Context::Prefix(ctx) => {
+            // Prefixed command, just add a thumbsup reaction
+            ctx.msg.react(ctx.http(), '👍').await?;
+        }
+        poise::Context::Application(_) => {
+            // Slash command, no message to react to, just say thumbsup
+            ctx.say("👍").await?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Clears the message queue!
 #[poise::command(
-    aliases("skip"),
     category = "Main Commands",
     guild_only,
     prefix_command,
@@ -299,17 +328,7 @@ pub async fn clear(ctx: Context<'_>) -> CommandResult {
     let guild_id = ctx.guild_id().unwrap();
     if let Some(call_lock) = ctx.data().songbird.get(guild_id) {
         call_lock.lock().await.queue().stop();
-
-        match ctx {
-            poise::Context::Prefix(ctx) => {
-                // Prefixed command, just add a thumbsup reaction
-                ctx.msg.react(ctx.http(), '👍').await?;
-            }
-            poise::Context::Application(_) => {
-                // Slash command, no message to react to, just say thumbsup
-                ctx.say("👍").await?;
-            }
-        }
+        acknowledge(ctx).await?;
     } else {
         ctx.say("**Error**: I am not in a voice channel!").await?;
     }
@@ -317,6 +336,42 @@ pub async fn clear(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
-pub fn commands() -> [Command; 3] {
-    [join(), leave(), clear()]
+/// Skips the message currently being read, use clear to skip every queued message!
+#[poise::command(
+    category = "Main Commands",
+    guild_only,
+    prefix_command,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES | ADD_REACTIONS"
+)]
+pub async fn skip(ctx: Context<'_>) -> CommandResult {
+    if channel_check(&ctx, ctx.author_vc()).await?.is_none() {
+        return Ok(());
+    }
+
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+    let Some(call_lock) = data.songbird.get(guild_id) else {
+        ctx.say("**Error**: I am not in a voice channel!").await?;
+        return Ok(());
+    };
+
+    let Some(current) = call_lock.lock().await.queue().current() else {
+        ctx.say("**Error**: I am not reading anything!").await?;
+        return Ok(());
+    };
+
+    // Long messages are split into multiple tracks, so skip every chunk of the current message.
+    if let Some(queued_message) = data.queued_messages.remove_by_track(guild_id, &current) {
+        queued_message.stop();
+    } else {
+        current.stop()?;
+    }
+
+    acknowledge(ctx).await?;
+    Ok(())
+}
+
+pub fn commands() -> [Command; 4] {
+    [join(), leave(), clear(), skip()]
 }
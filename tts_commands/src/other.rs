@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use aformat::{aformat, astr};
 use anyhow::Error;
 use num_format::{Locale, ToFormattedString};
@@ -15,7 +17,7 @@ use tts_core::{
     constants::OPTION_SEPERATORS,
     opt_ext::OptionTryUnwrap,
     require_guild,
-    structs::{ApplicationContext, Command, CommandResult, Context, IsPremium, TTSMode},
+    structs::{ApplicationContext, Command, CommandResult, Context, TTSMode},
     traits::PoiseContextExt as _,
 };
 
@@ -98,19 +100,25 @@ async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> Comma
         let data = ctx.data();
         let http = ctx.http();
         let guild_info = if let Some(guild_id) = ctx.guild_id() {
-            Some((guild_id, data.is_premium_simple(http, guild_id).await?))
+            Some((guild_id, data.guild_premium(http, guild_id).await?))
         } else {
             None
         };
 
-        let (voice, mode) = data
+        let (mut voice, mut mode) = data
             .parse_user_or_guild_with_premium(author.id, guild_info)
             .await?;
 
+        let premium = guild_info.and_then(|(_, premium)| premium);
+        if let Some(fallback) = data.fallback_mode(mode, premium) {
+            voice = Cow::Borrowed(fallback.default_voice());
+            mode = fallback;
+        }
+
         let guild_row;
-        let translation_lang = if let Some((guild_id, is_premium)) = guild_info {
+        let translation_lang = if let Some((guild_id, premium)) = guild_info {
             guild_row = data.guilds_db.get(guild_id.into()).await?;
-            guild_row.target_lang(IsPremium::from(is_premium))
+            data.target_lang(&guild_row, premium)
         } else {
             None
         };
@@ -132,8 +140,7 @@ async fn tts_(ctx: Context<'_>, author: &serenity::User, message: &str) -> Comma
             translation_lang,
         );
 
-        let auth_key = data.config.tts_service_auth_key.as_deref();
-        let audio = fetch_audio(&data.reqwest, url, auth_key)
+        let audio = fetch_audio(&data, url, mode)
             .await?
             .try_unwrap()?
             .bytes()
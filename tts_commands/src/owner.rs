@@ -144,6 +144,24 @@ pub async fn refresh_ofs(ctx: Context<'_>) -> CommandResult {
     Ok(())
 }
 
+#[poise::command(prefix_command, owners_only, hide_in_help, aliases("reload_voices"))]
+pub async fn refresh_voices(ctx: Context<'_>) -> CommandResult {
+    let diff = tts_core::voices::reload(ctx.data()).await?;
+
+    ctx.say(
+        aformat!(
+            "Done! Added {} and removed {} voices, added {} and removed {} translation languages!",
+            diff.added_voices.len(),
+            diff.removed_voices.len(),
+            diff.added_languages.len(),
+            diff.removed_languages.len(),
+        )
+        .as_str(),
+    )
+    .await?;
+    Ok(())
+}
+
 /// Debug commands for the bot
 #[poise::command(
     prefix_command,
@@ -367,13 +385,14 @@ pub async fn cache_info(ctx: Context<'_>, kind: Option<String>) -> CommandResult
     Ok(())
 }
 
-pub fn commands() -> [Command; 6] {
+pub fn commands() -> [Command; 7] {
     [
         dm(),
         debug(),
         register(),
         remove_cache(),
         refresh_ofs(),
+        refresh_voices(),
         cache_info(),
     ]
 }
@@ -1,4 +1,4 @@
-use std::fmt::Write as _;
+use std::{borrow::Cow, fmt::Write as _};
 
 use aformat::{aformat, aformat_into, ArrayString};
 
@@ -11,7 +11,9 @@ use poise::{
 use tts_core::{
     common::remove_premium,
     constants::PREMIUM_NEUTRAL_COLOUR,
+    database_models::GuildColumn,
     opt_ext::OptionTryUnwrap as _,
+    storage::Backend,
     structs::{Command, CommandResult, Context, Result, TTSMode},
     traits::PoiseContextExt,
 };
@@ -22,7 +24,7 @@ struct GuildIdRow {
 }
 
 fn get_premium_guilds<'a>(
-    conn: impl sqlx::PgExecutor<'a> + 'a,
+    conn: impl sqlx::Executor<'a, Database = Backend> + 'a,
     premium_user: serenity::UserId,
 ) -> BoxStream<'a, Result<GuildIdRow, sqlx::Error>> {
     sqlx::query_as("SELECT guild_id FROM guilds WHERE premium_user = $1")
@@ -31,7 +33,7 @@ fn get_premium_guilds<'a>(
 }
 
 async fn get_premium_guild_count<'a>(
-    conn: impl sqlx::PgExecutor<'a> + 'a,
+    conn: impl sqlx::Executor<'a, Database = Backend> + 'a,
     premium_user: serenity::UserId,
 ) -> Result<i64> {
     let guilds = get_premium_guilds(conn, premium_user);
@@ -96,10 +98,17 @@ pub async fn premium_activate(ctx: Context<'_>) -> CommandResult {
 
     let author = ctx.author();
     let linked_guilds = get_premium_guild_count(&data.pool, author.id).await?;
-    let error_msg = match data.fetch_premium_info(ctx.http(), author.id).await? {
-        Some(tier) => {
-            if linked_guilds >= tier.entitled_servers.get().into() {
-                Some(Cow::Owned(format!("Hey, you already have {linked_guilds} servers linked, you are only subscribed to the {} tier!", tier.entitled_servers)))
+    let premium_info = data.fetch_premium_info(ctx.http(), author.id).await?;
+    let tier = premium_info.and_then(|info| data.premium_tier(info));
+    let error_msg = match premium_info {
+        Some(info) => {
+            if linked_guilds >= info.entitled_servers.get().into() {
+                let tier_name = match tier {
+                    Some(tier) => Cow::Borrowed(tier.name.as_str()),
+                    None => Cow::Owned(info.entitled_servers.to_string()),
+                };
+
+                Some(Cow::Owned(format!("Hey, you already have {linked_guilds} servers linked, you are only subscribed to the {tier_name} tier!")))
             } else {
                 None
             }
@@ -124,13 +133,19 @@ pub async fn premium_activate(ctx: Context<'_>) -> CommandResult {
         return Ok(());
     }
 
+    // Switch to the first mode of the tier, which is gCloud unless configured otherwise
+    let voice_mode = match tier {
+        Some(tier) => tier.modes.first().copied().unwrap_or_default(),
+        None => TTSMode::gCloud,
+    };
+
     let author_id = author.id.get() as i64;
     data.userinfo_db.create_row(author_id).await?;
     data.guilds_db
-        .set_one(guild_id.into(), "premium_user", &author_id)
-        .await?;
-    data.guilds_db
-        .set_one(guild_id.into(), "voice_mode", &TTSMode::gCloud)
+        .set_many(guild_id.into())
+        .set(GuildColumn::PremiumUser, author_id)
+        .set(GuildColumn::VoiceMode, voice_mode)
+        .execute()
         .await?;
 
     ctx.say("Done! This server is now premium!").await?;
@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use poise::serenity_prelude as serenity;
+
+use tts_core::{
+    database_models::{GuildColumn, UserColumn},
+    structs::{ApplicationContext, CommandResult, Context, Data},
+    traits::PoiseContextExt as _,
+};
+
+/// Finds the supported locale matching either a locale code or a language name
+fn find_language<'a>(data: &'a Data, language: &str) -> Option<(&'a str, &'a str)> {
+    data.translations.languages().find(|(locale, name)| {
+        locale.eq_ignore_ascii_case(language) || name.eq_ignore_ascii_case(language)
+    })
+}
+
+fn unsupported_language(data: &Data, language: &str) -> String {
+    let mut languages: Vec<_> = data
+        .translations
+        .languages()
+        .map(|(locale, name)| format!("`{name}` ({locale})"))
+        .collect();
+
+    languages.sort_unstable();
+    let languages = languages.join(", ");
+
+    data.translations
+        .get(language, "language.unsupported")
+        .replace("{languages}", &languages)
+}
+
+#[expect(clippy::unused_async)]
+async fn language_autocomplete<'a>(
+    ctx: ApplicationContext<'a>,
+    searching: &'a str,
+) -> serenity::CreateAutocompleteResponse<'a> {
+    let data = ctx.serenity_context().data_ref::<Data>();
+    let searching = searching.to_lowercase();
+
+    let mut languages: Vec<_> = data
+        .translations
+        .languages()
+        .filter(|(locale, name)| {
+            locale.to_lowercase().starts_with(&searching)
+                || name.to_lowercase().starts_with(&searching)
+        })
+        .collect();
+
+    languages.sort_unstable();
+    serenity::CreateAutocompleteResponse::new().set_choices(
+        languages
+            .into_iter()
+            .take(25)
+            .map(|(locale, name)| {
+                serenity::AutocompleteChoice::new(format!("{name} ({locale})"), locale)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Changes the language the bot replies to you in
+#[poise::command(
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("interface_language", "ui_language")
+)]
+pub async fn bot_language(
+    ctx: Context<'_>,
+    #[description = "The language for the bot to reply in, leave blank to reset"]
+    #[autocomplete = "language_autocomplete"]
+    language: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let user_id = ctx.author().id.into();
+
+    let to_send = if let Some(language) = language {
+        if let Some((locale, name)) = find_language(&data, &language) {
+            data.userinfo_db.create_row(user_id).await?;
+            data.userinfo_db
+                .set_one(user_id, UserColumn::Language, locale)
+                .await?;
+
+            let language = ctx.language().await?;
+            let msg = data.translations.get(language, "language.user_set");
+            Cow::Owned(msg.replace("{name}", name))
+        } else {
+            Cow::Owned(unsupported_language(&data, ctx.language().await?))
+        }
+    } else {
+        data.userinfo_db
+            .set_one(user_id, UserColumn::Language, None::<String>)
+            .await?;
+
+        let language = ctx.language().await?;
+        Cow::Borrowed(data.translations.get(language, "language.user_reset"))
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
+/// Changes the language the bot replies to this server in
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("server_interface_language", "server_ui_language")
+)]
+pub async fn server_bot_language(
+    ctx: Context<'_>,
+    #[description = "The language for the bot to reply in, leave blank to reset"]
+    #[autocomplete = "language_autocomplete"]
+    language: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap().into();
+
+    let to_send = if let Some(language) = language {
+        if let Some((locale, name)) = find_language(&data, &language) {
+            data.guilds_db
+                .set_one(guild_id, GuildColumn::Language, locale)
+                .await?;
+
+            let language = ctx.language().await?;
+            let msg = data.translations.get(language, "language.guild_set");
+            Cow::Owned(msg.replace("{name}", name))
+        } else {
+            Cow::Owned(unsupported_language(&data, ctx.language().await?))
+        }
+    } else {
+        data.guilds_db
+            .set_one(guild_id, GuildColumn::Language, None::<String>)
+            .await?;
+
+        let language = ctx.language().await?;
+        Cow::Borrowed(data.translations.get(language, "language.guild_reset"))
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
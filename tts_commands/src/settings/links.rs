@@ -0,0 +1,137 @@
+use std::borrow::Cow;
+
+use tts_core::{
+    database_models::GuildColumn,
+    structs::{CommandResult, Context, LinkReadingMode},
+    traits::PoiseContextExt as _,
+};
+
+const MAX_LINK_NAMES: i64 = 50;
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Strips the scheme, `www.`, and path from a domain, returning `None` if it doesn't look like one
+fn parse_domain(domain: &str) -> Option<String> {
+    let domain = domain.trim().to_lowercase();
+    let domain = domain.split_once("://").map(|(_, d)| d).unwrap_or(&domain);
+    let domain = domain.split('/').next()?;
+    let domain = domain.strip_prefix("www.").unwrap_or(domain);
+
+    let is_valid = domain.len() <= 64
+        && domain.contains('.')
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+
+    is_valid.then(|| domain.to_owned())
+}
+
+/// Changes how links in messages are read out
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("links", "link_mode", "read_links")
+)]
+pub async fn link_reading(
+    ctx: Context<'_>,
+    #[description = "How links should be read out"] mode: LinkReadingMode,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    data.guilds_db
+        .set_one(guild_id.into(), GuildColumn::LinkReading, mode as i16)
+        .await?;
+
+    let key = match mode {
+        LinkReadingMode::Drop => "links.drop",
+        LinkReadingMode::Domain => "links.domain",
+        LinkReadingMode::KnownSites => "links.known_sites",
+    };
+
+    let language = ctx.language().await?;
+    ctx.say(data.translations.get(language, key)).await?;
+    Ok(())
+}
+
+/// Changes the name said for links to a website, when reading known websites
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("link_names", "site_name")
+)]
+pub async fn link_name(
+    ctx: Context<'_>,
+    #[description = "The website's domain, such as example.com"] domain: String,
+    #[description = "The name to say for links to the website, leave blank to remove"]
+    #[rest]
+    name: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    let Some(domain) = parse_domain(&domain) else {
+        ctx.say(t("links.invalid_domain")).await?;
+        return Ok(());
+    };
+
+    let to_send = if let Some(name) = name {
+        if name.chars().count() > MAX_NAME_LENGTH {
+            let max_length = MAX_NAME_LENGTH.to_string();
+            Cow::Owned(t("links.name_too_long").replace("{max_length}", &max_length))
+        } else if name.contains('<') && name.contains('>') {
+            Cow::Borrowed(t("links.name_mentions"))
+        } else {
+            let (existing,): (i64,) = sqlx::query_as(
+                "SELECT count(*) FROM link_names WHERE guild_id = $1 AND domain != $2",
+            )
+            .bind(guild_id.get() as i64)
+            .bind(&domain)
+            .fetch_one(&data.pool)
+            .await?;
+
+            if existing >= MAX_LINK_NAMES {
+                let max_names = MAX_LINK_NAMES.to_string();
+                Cow::Owned(t("links.too_many").replace("{max_names}", &max_names))
+            } else {
+                data.guilds_db.create_row(guild_id.into()).await?;
+                sqlx::query(
+                    "
+                    INSERT INTO link_names(guild_id, domain, name) VALUES ($1, $2, $3)
+                    ON CONFLICT (guild_id, domain) DO UPDATE SET name = EXCLUDED.name
+                ",
+                )
+                .bind(guild_id.get() as i64)
+                .bind(&domain)
+                .bind(&name)
+                .execute(&data.pool)
+                .await?;
+
+                let msg = t("links.name_set").replace("{domain}", &domain);
+                Cow::Owned(msg.replace("{name}", &name))
+            }
+        }
+    } else {
+        sqlx::query("DELETE FROM link_names WHERE guild_id = $1 AND domain = $2")
+            .bind(guild_id.get() as i64)
+            .bind(&domain)
+            .execute(&data.pool)
+            .await?;
+
+        Cow::Owned(t("links.name_removed").replace("{domain}", &domain))
+    };
+
+    data.link_names_cache.invalidate(&guild_id);
+    ctx.say(to_send).await?;
+    Ok(())
+}
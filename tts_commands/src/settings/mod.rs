@@ -1,8 +1,16 @@
+mod language;
+mod links;
+mod names;
 mod owner;
+mod preview;
 mod setup;
+mod stage;
+mod voice_announcements;
 mod voice_paginator;
+mod voice_search;
+mod xsaid_template;
 
-use std::{borrow::Cow, collections::HashMap, fmt::Write, sync::atomic::Ordering};
+use std::{borrow::Cow, collections::HashMap, sync::atomic::Ordering};
 
 use aformat::{aformat, ToArrayString};
 use arrayvec::ArrayString;
@@ -15,26 +23,36 @@ use serenity::{
 };
 
 use tts_core::{
-    common::{confirm_dialog, random_footer},
-    constants::{GTTS_DISABLED_ERROR, OPTION_SEPERATORS, PREMIUM_NEUTRAL_COLOUR},
+    common::{confirm_dialog, random_footer, readable_name},
+    constants::{GTTS_DISABLED_ERROR, MAX_XSAID_WINDOW, OPTION_SEPERATORS, PREMIUM_NEUTRAL_COLOUR},
     database::{self, Compact},
-    require_guild,
+    database_models::{GuildColumn, GuildVoiceColumn, NicknameColumn, UserColumn, UserVoiceColumn},
+    opt_ext::OptionTryUnwrap as _,
+    require_guild, storage,
     structs::{
-        ApplicationContext, Command, CommandResult, Context, Data, Error, Result, SpeakingRateInfo,
-        TTSMode, TTSModeChoice,
+        ApplicationContext, Command, CommandResult, Context, Data, Error, GuildPremium, Result,
+        SpeakingRateInfo, TTSMode, TTSModeChoice,
     },
     traits::PoiseContextExt,
 };
 
-use self::voice_paginator::MenuPaginator;
+use self::{
+    voice_paginator::{MenuPaginator, VoicePage},
+    voice_search::{
+        format_recommended, paginate, recommended, voice_entries, VoiceFilter, VoiceGender,
+    },
+};
 
 fn format_voice<'a>(data: &Data, voice: &'a str, mode: TTSMode) -> Cow<'a, str> {
-    if mode == TTSMode::gCloud {
-        let (lang, variant) = voice.split_once(' ').unwrap();
-        let gender = &data.gcloud_voices[lang][variant];
+    let voices = data.voices.load();
+    if mode == TTSMode::gCloud
+        && let Some((lang, variant)) = voice.split_once(' ')
+        && let Some(gender) = voices.gcloud_voices.get(lang).and_then(|v| v.get(variant))
+    {
         Cow::Owned(format!("{lang} - {variant} ({gender})"))
-    } else if mode == TTSMode::Polly {
-        let voice = &data.polly_voices[voice];
+    } else if mode == TTSMode::Polly
+        && let Some(voice) = voices.polly_voices.get(voice)
+    {
         Cow::Owned(format!(
             "{} - {} ({})",
             voice.name, voice.language_name, voice.gender
@@ -57,7 +75,9 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
     let author_id = ctx.author().id;
 
     let data = ctx.data();
-    let none_str = "none";
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+    let none_str = t("settings.none");
 
     let guild_row = data.guilds_db.get(guild_id.into()).await?;
     let userinfo_row = data.userinfo_db.get(author_id.into()).await?;
@@ -65,6 +85,7 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
         .nickname_db
         .get([guild_id.into(), author_id.into()])
         .await?;
+    let voice_announcements_row = data.voice_announcements_db.get(guild_id.into()).await?;
 
     let channel_mention = if let Some(channel) = guild_row.channel
         && require_guild!(ctx).channels.contains_key(&channel)
@@ -136,63 +157,155 @@ pub async fn settings(ctx: Context<'_>) -> CommandResult {
     let [sep1, sep2, sep3, sep4] = OPTION_SEPERATORS;
 
     let xsaid = guild_row.xsaid();
+    let xsaid_reannounce = guild_row.xsaid_reannounce();
+    let name_source: &str = guild_row.name_source.into();
     let autojoin = guild_row.auto_join();
-    let msg_length = guild_row.msg_length;
     let bot_ignore = guild_row.bot_ignore();
     let skip_emoji = guild_row.skip_emoji();
+    let describe_messages = guild_row.describe_messages();
+    let read_edits = guild_row.read_edits();
+    let read_threads = guild_row.read_threads();
+    let link_reading: &str = guild_row.link_reading.into();
     let guild_mode: &str = guild_mode.into();
     let to_translate = guild_row.to_translate();
     let require_voice = guild_row.require_voice();
     let text_in_voice = guild_row.text_in_voice();
     let audience_ignore = guild_row.audience_ignore();
+    let stage_reading: &str = guild_row.stage_reading.into();
+    let stage_auto_speak = guild_row.stage_auto_speak();
+    let announce_speakers = guild_row.announce_speakers();
+    let announce_joins = voice_announcements_row.announce_joins;
+    let announce_leaves = voice_announcements_row.announce_leaves;
     let voice_mode = user_mode.map(Into::into).unwrap_or(none_str);
     let role_mention = required_role.as_deref().unwrap_or(none_str);
     let required_prefix = guild_row.required_prefix.as_deref().unwrap_or(none_str);
+    let xsaid_template = guild_row.xsaid_template.as_deref().unwrap_or(none_str);
+    let server_language = guild_row.language.as_deref().unwrap_or(none_str);
+    let user_language = userinfo_row.language.as_deref().unwrap_or(none_str);
+    let msg_length =
+        t("settings.seconds").replace("{seconds}", &guild_row.msg_length.to_arraystring());
+    let xsaid_window =
+        t("settings.seconds").replace("{seconds}", &guild_row.xsaid_window.to_arraystring());
+    let idle_timeout = match guild_row.idle_timeout {
+        Some(minutes) => {
+            Cow::Owned(t("settings.minutes").replace("{minutes}", &minutes.get().to_arraystring()))
+        }
+        None => Cow::Borrowed(t("settings.disabled")),
+    };
     let repeated_chars = match guild_row.repeated_chars {
         Some(chars) => &chars.to_arraystring(),
-        None => "Disabled",
+        None => t("settings.disabled"),
     };
 
-    ctx.send(poise::CreateReply::default().embed(CreateEmbed::default()
-        .title("Current Settings")
-        .colour(neutral_colour)
-        .url(data.config.main_server_invite.as_str())
-        .footer(CreateEmbedFooter::new(
-            "Change these settings with `/set {property} {value}`!\nNone = setting has not been set yet!"
-        ))
-        .field("**General Server Settings**", format!("
-{sep1} Setup Channel: {channel_mention}
-{sep1} Required Role: {role_mention}
-{sep1} Command Prefix: `{prefix}`
-{sep1} Auto Join: `{autojoin}`"), false)
-        .field("**TTS Settings**", format!("
-{sep2} <User> said: message: `{xsaid}`
-{sep2} Ignore bot's messages: `{bot_ignore}`
-{sep2} Ignore audience messages: `{audience_ignore}`
-{sep2} Require users in voice channel: `{require_voice}`
-{sep2} Required prefix for TTS: `{required_prefix}`
-{sep2} Read from Text in Voice channels: `{text_in_voice}`
-{sep2} Skip emojis when reading messages: `{skip_emoji}`
-
-**{sep2} Default Server Voice Mode: `{guild_mode}`**
-**{sep2} Default Server Voice: `{default_voice}`**
-
-{sep2} Max Time to Read: `{msg_length} seconds`
-{sep2} Max Repeated Characters: `{repeated_chars}`
-        "),        false)
-        .field("**Translation Settings (Premium Only)**", format!("
-{sep4} Translation: `{to_translate}`
-{sep4} Translation Language: `{target_lang}`
-        ")
-        ,false)
-        .field("**User Specific**", format!("
-{sep3} Voice: `{user_voice}`
-{sep3} Voice Mode: `{voice_mode}`
-{sep3} Nickname: `{nickname}`
-{sep3} Speaking Rate: `{speaking_rate}{speaking_rate_kind}`
-        "),
-        false)
-    )).await?;
+    let general = format!(
+        "
+{sep1} {}: {channel_mention}
+{sep1} {}: {role_mention}
+{sep1} {}: `{prefix}`
+{sep1} {}: `{autojoin}`
+{sep1} {}: `{idle_timeout}`
+{sep1} {}: `{server_language}`",
+        t("settings.setup_channel"),
+        t("settings.required_role"),
+        t("settings.command_prefix"),
+        t("settings.auto_join"),
+        t("settings.idle_timeout"),
+        t("settings.language"),
+    );
+
+    let tts = format!(
+        "
+{sep2} {}: `{xsaid}`
+{sep2} {}: `{xsaid_window}`
+{sep2} {}: `{xsaid_reannounce}`
+{sep2} {}: `{xsaid_template}`
+{sep2} {}: `{name_source}`
+{sep2} {}: `{bot_ignore}`
+{sep2} {}: `{audience_ignore}`
+{sep2} {}: `{stage_reading}`
+{sep2} {}: `{stage_auto_speak}`
+{sep2} {}: `{announce_speakers}`
+{sep2} {}: `{require_voice}`
+{sep2} {}: `{required_prefix}`
+{sep2} {}: `{text_in_voice}`
+{sep2} {}: `{skip_emoji}`
+{sep2} {}: `{describe_messages}`
+{sep2} {}: `{read_edits}`
+{sep2} {}: `{read_threads}`
+{sep2} {}: `{link_reading}`
+{sep2} {}: `{announce_joins}`
+{sep2} {}: `{announce_leaves}`
+
+**{sep2} {}: `{guild_mode}`**
+**{sep2} {}: `{default_voice}`**
+
+{sep2} {}: `{msg_length}`
+{sep2} {}: `{repeated_chars}`
+        ",
+        t("settings.xsaid"),
+        t("settings.xsaid_window"),
+        t("settings.xsaid_reannounce"),
+        t("settings.xsaid_template"),
+        t("settings.name_source"),
+        t("settings.bot_ignore"),
+        t("settings.audience_ignore"),
+        t("settings.stage_reading"),
+        t("settings.stage_auto_speak"),
+        t("settings.announce_speakers"),
+        t("settings.require_voice"),
+        t("settings.required_prefix"),
+        t("settings.text_in_voice"),
+        t("settings.skip_emoji"),
+        t("settings.describe_messages"),
+        t("settings.read_edits"),
+        t("settings.read_threads"),
+        t("settings.link_reading"),
+        t("settings.announce_joins"),
+        t("settings.announce_leaves"),
+        t("settings.default_mode"),
+        t("settings.default_voice"),
+        t("settings.msg_length"),
+        t("settings.repeated_chars"),
+    );
+
+    let translation = format!(
+        "
+{sep4} {}: `{to_translate}`
+{sep4} {}: `{target_lang}`
+        ",
+        t("settings.to_translate"),
+        t("settings.target_lang"),
+    );
+
+    let user = format!(
+        "
+{sep3} {}: `{user_voice}`
+{sep3} {}: `{voice_mode}`
+{sep3} {}: `{nickname}`
+{sep3} {}: `{speaking_rate}{speaking_rate_kind}`
+{sep3} {}: `{user_language}`
+        ",
+        t("settings.voice"),
+        t("settings.voice_mode"),
+        t("settings.nickname"),
+        t("settings.speaking_rate"),
+        t("settings.user_language"),
+    );
+
+    ctx.send(
+        poise::CreateReply::default().embed(
+            CreateEmbed::default()
+                .title(t("settings.title"))
+                .colour(neutral_colour)
+                .url(data.config.main_server_invite.as_str())
+                .footer(CreateEmbedFooter::new(t("settings.footer")))
+                .field(t("settings.general"), general, false)
+                .field(t("settings.tts"), tts, false)
+                .field(t("settings.translation"), translation, false)
+                .field(t("settings.user"), user, false),
+        ),
+    )
+    .await?;
 
     Ok(())
 }
@@ -213,46 +326,33 @@ async fn voice_autocomplete<'a>(
         return serenity::CreateAutocompleteResponse::new();
     };
 
-    let voices: &mut dyn Iterator<Item = _> = match mode {
-        TTSMode::gTTS => &mut data
-            .gtts_voices
-            .iter()
-            .map(|(k, v)| (v.to_string(), k.to_string())),
-        TTSMode::eSpeak => &mut data
-            .espeak_voices
-            .iter()
-            .map(|voice| (voice.to_string(), voice.to_string())),
-        TTSMode::Polly => &mut data.polly_voices.values().map(|voice| {
-            let name = format!(
-                "{} - {} ({})",
-                voice.name, voice.language_name, voice.gender
-            );
+    let entries = voice_entries(data, mode);
+    let filter = VoiceFilter::parse(&entries, searching);
 
-            (name, voice.id.to_string())
-        }),
-        TTSMode::gCloud => &mut data.gcloud_voices.iter().flat_map(|(language, variants)| {
-            variants.iter().map(move |(variant, gender)| {
-                (
-                    format!("{language} {variant} ({gender})"),
-                    format!("{language} {variant}"),
-                )
-            })
-        }),
-    };
+    let mut voices = if filter.is_empty() {
+        let guild_locale = ctx.interaction.guild_locale.as_deref();
+        let locale = data
+            .locale(None, ctx.interaction.guild_id, guild_locale)
+            .await
+            .unwrap_or("en");
 
-    let searching_lower = searching.to_lowercase();
-    let mut voices: Vec<_> = voices
-        .map(|(label, value)| (label.to_lowercase(), label, value))
-        .collect();
+        let recommended_ids: Vec<_> = recommended(&entries, locale)
+            .into_iter()
+            .map(|entry| entry.id.clone())
+            .collect();
 
-    voices.sort_by_cached_key(|(l_lower, _, _)| strsim::levenshtein(l_lower, &searching_lower));
-    voices.sort_by_key(|(l_lower, _, _)| !l_lower.contains(&searching_lower));
+        let mut entries = entries;
+        entries.sort_by_key(|entry| !recommended_ids.contains(&entry.id));
+        entries
+    } else {
+        filter.apply(entries)
+    };
 
+    voices.truncate(25);
     serenity::CreateAutocompleteResponse::new().set_choices(
         voices
             .into_iter()
-            .take(25)
-            .map(|(_, label, value)| serenity::AutocompleteChoice::new(label, value))
+            .map(|entry| serenity::AutocompleteChoice::new(entry.label, entry.id))
             .collect::<Vec<_>>(),
     )
 }
@@ -263,7 +363,8 @@ async fn translation_languages_autocomplete<'a>(
     searching: &'a str,
 ) -> serenity::CreateAutocompleteResponse<'a> {
     let data = ctx.serenity_context().data_ref::<Data>();
-    let languages = data.translation_languages.iter();
+    let voices = data.voices.load();
+    let languages = voices.translation_languages.iter();
     let mut filtered_languages: Vec<_> = languages
         .filter(|(_, name)| name.starts_with(searching))
         .collect();
@@ -273,7 +374,9 @@ async fn translation_languages_autocomplete<'a>(
         filtered_languages
             .into_iter()
             .take(25)
-            .map(|(value, name)| serenity::AutocompleteChoice::new(name, value.as_str()))
+            .map(|(value, name)| {
+                serenity::AutocompleteChoice::new(name.to_string(), value.to_string())
+            })
             .collect::<Vec<_>>(),
     )
 }
@@ -294,7 +397,7 @@ enum Target {
 async fn can_change_mode(
     ctx: &Context<'_>,
     mode: Option<TTSMode>,
-    guild_is_premium: bool,
+    premium: Option<GuildPremium<'_>>,
 ) -> Result<bool> {
     let data = ctx.data();
     let Some(mode) = mode else { return Ok(true) };
@@ -304,7 +407,14 @@ async fn can_change_mode(
         return Ok(false);
     }
 
-    if mode.is_premium() && !guild_is_premium {
+    if mode.is_unlocked(premium) {
+        return Ok(true);
+    }
+
+    if premium.is_some() {
+        let msg = format!("The `{mode}` TTS Mode is not included in this server's premium tier!");
+        ctx.send_error(msg).await?;
+    } else {
         ctx.send(poise::CreateReply::default().embed(CreateEmbed::default()
             .title("TTS Bot Premium")
             .colour(PREMIUM_NEUTRAL_COLOUR)
@@ -316,11 +426,9 @@ async fn can_change_mode(
                 The `{mode}` TTS Mode is only for TTS Bot Premium subscribers, please check out the `/premium` command!
             ").as_str())
         )).await?;
-
-        Ok(false)
-    } else {
-        Ok(true)
     }
+
+    Ok(false)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -328,6 +436,7 @@ async fn change_voice<'a, T, RowT1, RowT2>(
     ctx: &'a Context<'a>,
     general_db: &'a database::Handler<T, RowT1>,
     voice_db: &'a database::Handler<(T, TTSMode), RowT2>,
+    voice_column: RowT2::Column,
     author_id: serenity::UserId,
     guild_id: serenity::GuildId,
     key: T,
@@ -335,8 +444,13 @@ async fn change_voice<'a, T, RowT1, RowT2>(
     target: Target,
 ) -> Result<Cow<'a, str>, Error>
 where
-    RowT1: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Compact + Send + Sync + Unpin,
-    RowT2: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Compact + Send + Sync + Unpin,
+    RowT1: for<'r> sqlx::FromRow<'r, storage::Row> + Compact + Send + Sync + Unpin,
+    RowT2: for<'r> sqlx::FromRow<'r, storage::Row>
+        + Compact
+        + database::HasColumns
+        + Send
+        + Sync
+        + Unpin,
 
     T: database::CacheKeyTrait + Default + Send + Sync + Copy,
     (T, TTSMode): database::CacheKeyTrait,
@@ -349,10 +463,10 @@ where
         if check_valid_voice(&data, &voice, mode) {
             general_db.create_row(key).await?;
             voice_db
-                .set_one((key, mode), "voice", voice.as_str())
+                .set_one((key, mode), voice_column, voice.as_str())
                 .await?;
 
-            let name = get_voice_name(&data, &voice, mode).unwrap_or(&voice);
+            let name = get_voice_name(&data, &voice, mode).unwrap_or(voice);
             Cow::Owned(match target {
                 Target::Guild => format!("Changed the server voice to: {name}"),
                 Target::User => format!("Changed your voice to {name}"),
@@ -385,23 +499,17 @@ fn format_languages<'a>(mut iter: impl Iterator<Item = &'a FixedString<u8>>) ->
     buf
 }
 
-fn get_voice_name<'a>(data: &'a Data, code: &str, mode: TTSMode) -> Option<&'a FixedString<u8>> {
+fn get_voice_name(data: &Data, code: &str, mode: TTSMode) -> Option<FixedString<u8>> {
+    let voices = data.voices.load();
     match mode {
-        TTSMode::gTTS => data.gtts_voices.get(code),
-        TTSMode::Polly => data.polly_voices.get(code).map(|n| &n.name),
+        TTSMode::gTTS => voices.gtts_voices.get(code).cloned(),
+        TTSMode::Polly => voices.polly_voices.get(code).map(|n| n.name.clone()),
         TTSMode::eSpeak | TTSMode::gCloud => None,
     }
 }
 
 fn check_valid_voice(data: &Data, code: &FixedString<u8>, mode: TTSMode) -> bool {
-    match mode {
-        TTSMode::gTTS | TTSMode::Polly => get_voice_name(data, code, mode).is_some(),
-        TTSMode::eSpeak => data.espeak_voices.contains(code),
-        TTSMode::gCloud => code
-            .split_once(' ')
-            .and_then(|(language, variant)| data.gcloud_voices.get(language).map(|l| (l, variant)))
-            .is_some_and(|(ls, v)| ls.contains_key(v)),
-    }
+    data.voices.load().contains(mode, code)
 }
 
 fn check_prefix(prefix: &str) -> Result<ArrayString<5>, &'static str> {
@@ -429,9 +537,8 @@ fn replace_bool(original: &str, value: bool) -> String {
 
 async fn generic_bool_command(
     ctx: Context<'_>,
-    key: &'static str,
+    column: GuildColumn,
     value: Option<bool>,
-    resp: &'static str,
 ) -> CommandResult {
     let Some(value) = bool_button(ctx, value).await? else {
         return Ok(());
@@ -440,8 +547,12 @@ async fn generic_bool_command(
     let guilds_db = &ctx.data().guilds_db;
     let guild_id = ctx.guild_id().unwrap();
 
-    guilds_db.set_one(guild_id.into(), key, &value).await?;
-    ctx.say(replace_bool(resp, value)).await?;
+    guilds_db.set_one(guild_id.into(), column, &value).await?;
+
+    let key: &str = column.into();
+    let state = if value { "enabled" } else { "disabled" };
+    ctx.say(format!("The setting `{key}` is now {state}."))
+        .await?;
 
     Ok(())
 }
@@ -450,15 +561,14 @@ macro_rules! create_bool_command {
     (
         $description:literal,
         $name:ident,
-        $key:literal,
+        $column:ident,
         aliases($( $aliases:literal ),*),
         $($extra:tt)*
     ) => {
         pub fn $name() -> Command {
-            const RESPONSE: &str = concat!("The setting `", $key, "` is now {}.");
             #[poise::command(prefix_command)]
             pub async fn prefix_bool(ctx: Context<'_>, value: Option<bool>) -> CommandResult {
-                generic_bool_command(ctx, $key, value, RESPONSE).await
+                generic_bool_command(ctx, GuildColumn::$column, value).await
             }
 
             #[doc=$description]
@@ -471,7 +581,7 @@ macro_rules! create_bool_command {
                 $($extra)*
             )]
             pub async fn slash_bool(ctx: Context<'_>, #[description = "True or False?"] value: bool) -> CommandResult {
-                generic_bool_command(ctx, $key, Some(value), RESPONSE).await
+                generic_bool_command(ctx, GuildColumn::$column, Some(value)).await
             }
 
             Command {
@@ -486,49 +596,91 @@ macro_rules! create_bool_command {
 create_bool_command!(
     "Makes the bot say \"<user> said\" before each message",
     xsaid,
-    "xsaid",
+    Xsaid,
     aliases(),
 );
+create_bool_command!(
+    "Makes the bot say a member's name again after someone else has spoken",
+    xsaid_reannounce,
+    XsaidReannounce,
+    aliases("reannounce_names"),
+);
 create_bool_command!(
     "Makes the bot join the voice channel automatically when a message is sent in the setup channel",
     autojoin,
-    "auto_join",
+    AutoJoin,
     aliases("auto_join"),
 );
 create_bool_command!(
     "Makes the bot ignore messages sent by bots and webhooks",
     botignore,
-    "bot_ignore",
+    BotIgnore,
     aliases("bot_ignore", "ignore_bots", "ignorebots"),
 );
 create_bool_command!(
     "Makes the bot require people to be in the voice channel to TTS",
     require_voice,
-    "require_voice",
+    RequireVoice,
     aliases("voice_require", "require_in_vc"),
 );
 create_bool_command!(
     "Makes the bot ignore messages sent by members of the audience in stage channels",
     audience_ignore,
-    "audience_ignore",
+    AudienceIgnore,
     aliases("audienceignore", "ignore_audience", "ignoreaudience"),
 );
 create_bool_command!(
     "Makes the bot read messages from text-in-voice channels",
     text_in_voice,
-    "text_in_voice",
+    TextInVoice,
     aliases(),
 );
 create_bool_command!(
     "Makes the bot skip emoji within messages",
     skip_emoji,
-    "skip_emoji",
+    SkipEmoji,
     aliases("skip_emojis"),
 );
+create_bool_command!(
+    "Makes the bot describe replies, stickers, polls, and files by name",
+    describe_messages,
+    DescribeMessages,
+    aliases("read_replies", "read_stickers"),
+);
+create_bool_command!(
+    "Makes the bot read edited messages again if they were edited before being read",
+    read_edits,
+    ReadEdits,
+    aliases("reread_edits", "edits"),
+);
+create_bool_command!(
+    "Makes the bot read messages in threads of the setup channel",
+    read_threads,
+    ReadThreads,
+    aliases("threads"),
+);
+create_bool_command!(
+    "Makes the bot become a speaker, or request to speak, when joining a stage channel",
+    stage_auto_speak,
+    StageAutoSpeak,
+    aliases("request_to_speak", "auto_speak"),
+);
+create_bool_command!(
+    "Makes the bot announce members becoming speakers or moving to the audience in stages",
+    announce_speakers,
+    AnnounceSpeakers,
+    aliases("speaker_announcements"),
+);
+create_bool_command!(
+    "Makes the bot say that it is leaving when disconnecting due to inactivity",
+    idle_leave_message,
+    IdleLeaveMessage,
+    aliases("inactivity_message"),
+);
 create_bool_command!(
     "Makes the bot translate all TTS messages to the same language",
     translation,
-    "to_translate",
+    ToTranslate,
     aliases("translate", "to_translate", "should_translate"),
     check = "crate::premium_command_check",
 );
@@ -546,7 +698,9 @@ async fn use_new_formatting(
     let id = ctx.author().id.into();
     let userinfo = &ctx.data().userinfo_db;
 
-    userinfo.set_one(id, "use_new_formatting", value).await?;
+    userinfo
+        .set_one(id, UserColumn::UseNewFormatting, value)
+        .await?;
 
     let resp = "Experimental new message formatting is now: {}";
     ctx.say(replace_bool(resp, value)).await?;
@@ -625,7 +779,7 @@ pub async fn required_role(
             .guilds_db
             .set_one(
                 guild_id.into(),
-                "required_role",
+                GuildColumn::RequiredRole,
                 &required_role.as_ref().map(|r| r.id.get() as i64),
             )
             .await?;
@@ -679,7 +833,7 @@ async fn required_prefix(
     let guild_id = ctx.guild_id().unwrap();
     ctx.data()
         .guilds_db
-        .set_one(guild_id.into(), "required_prefix", &tts_prefix)
+        .set_one(guild_id.into(), GuildColumn::RequiredPrefix, &tts_prefix)
         .await?;
 
     let msg = if let Some(tts_prefix) = prefix {
@@ -710,13 +864,13 @@ pub async fn server_mode(
     let guild_id = ctx.guild_id().unwrap();
 
     let mode = mode.map(TTSMode::from);
-    let guild_is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
-    if !can_change_mode(&ctx, mode, guild_is_premium).await? {
+    let premium = data.guild_premium(ctx.http(), guild_id).await?;
+    if !can_change_mode(&ctx, mode, premium).await? {
         return Ok(());
     }
 
     data.guilds_db
-        .set_one(guild_id.into(), "voice_mode", mode)
+        .set_one(guild_id.into(), GuildColumn::VoiceMode, mode)
         .await?;
 
     let response = if let Some(mode) = mode {
@@ -759,6 +913,7 @@ pub async fn server_voice(
         &ctx,
         &data.guilds_db,
         &data.guild_voice_db,
+        GuildVoiceColumn::Voice,
         ctx.author().id,
         guild_id,
         guild_id.into(),
@@ -792,11 +947,13 @@ pub async fn translation_lang(
     let guild_id = ctx.guild_id().unwrap().into();
 
     let to_say = if target_lang.as_ref().is_none_or(|target_lang| {
-        data.translation_languages
+        data.voices
+            .load()
+            .translation_languages
             .contains_key(target_lang.as_str())
     }) {
         data.guilds_db
-            .set_one(guild_id, "target_lang", &target_lang)
+            .set_one(guild_id, GuildColumn::TargetLang, &target_lang)
             .await?;
         if let Some(target_lang) = target_lang {
             let mut to_say = format!("The target translation language is now: `{target_lang}`");
@@ -835,7 +992,11 @@ pub async fn command_prefix(
         Ok(prefix) => {
             ctx.data()
                 .guilds_db
-                .set_one(ctx.guild_id().unwrap().into(), "prefix", prefix.as_str())
+                .set_one(
+                    ctx.guild_id().unwrap().into(),
+                    GuildColumn::Prefix,
+                    prefix.as_str(),
+                )
                 .await?;
 
             &aformat!("Command prefix for this server is now: {prefix}")
@@ -868,7 +1029,7 @@ pub async fn repeated_characters(
         let guild_id = ctx.guild_id().unwrap().into();
         ctx.data()
             .guilds_db
-            .set_one(guild_id, "repeated_chars", &(chars as i16))
+            .set_one(guild_id, GuildColumn::RepeatedChars, &(chars as i16))
             .await?;
 
         &aformat!("Max repeated characters is now: {chars}")
@@ -878,6 +1039,44 @@ pub async fn repeated_characters(
     Ok(())
 }
 
+/// Changes how long the bot waits before saying a member's name again (0 = always)
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("xsaid_timeout", "name_window")
+)]
+pub async fn xsaid_window(
+    ctx: Context<'_>,
+    #[description = "Seconds between a member's messages before their name is said again"]
+    seconds: u16,
+) -> CommandResult {
+    let to_send: &str = if seconds > MAX_XSAID_WINDOW {
+        &aformat!("**Error**: Cannot set the xsaid window above {MAX_XSAID_WINDOW} seconds")
+    } else {
+        ctx.data()
+            .guilds_db
+            .set_one(
+                ctx.guild_id().unwrap().into(),
+                GuildColumn::XsaidWindow,
+                &(seconds as i16),
+            )
+            .await?;
+
+        if seconds == 0 {
+            "The bot will now say the name of the author before every message"
+        } else {
+            &aformat!("The bot will now say a member's name again after {seconds} seconds")
+        }
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
 /// Changes the max length of a TTS message in seconds
 #[poise::command(
     guild_only,
@@ -901,7 +1100,7 @@ pub async fn msg_length(
             .guilds_db
             .set_one(
                 ctx.guild_id().unwrap().into(),
-                "msg_length",
+                GuildColumn::MsgLength,
                 &(seconds as i16),
             )
             .await?;
@@ -913,6 +1112,43 @@ pub async fn msg_length(
     Ok(())
 }
 
+/// Changes how long the bot stays in voice with nothing being said
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("inactivity_timeout", "idle_time")
+)]
+pub async fn idle_timeout(
+    ctx: Context<'_>,
+    #[description = "Minutes of inactivity before leaving, 0 to disable"] minutes: u16,
+) -> CommandResult {
+    let to_send = if minutes > 1440 {
+        "**Error**: Cannot set the idle timeout above 1440 minutes (1 day)"
+    } else {
+        ctx.data()
+            .guilds_db
+            .set_one(
+                ctx.guild_id().unwrap().into(),
+                GuildColumn::IdleTimeout,
+                &(minutes as i16),
+            )
+            .await?;
+
+        if minutes == 0 {
+            "The bot will no longer leave due to inactivity"
+        } else {
+            &aformat!("The bot will now leave after {minutes} minutes of inactivity")
+        }
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
+
 /// Changes the multiplier for how fast to speak
 #[poise::command(
     category = "Settings",
@@ -957,7 +1193,7 @@ pub async fn speaking_rate(
         data.user_voice_db
             .set_one(
                 (author.id.get() as i64, mode),
-                "speaking_rate",
+                UserVoiceColumn::SpeakingRate,
                 &speaking_rate,
             )
             .await?;
@@ -996,10 +1232,22 @@ pub async fn nick(
     }
 
     let data = ctx.data();
+    let nickname_row = data
+        .nickname_db
+        .get([guild_id.into(), user.id.into()])
+        .await?;
+
+    if nickname_row.locked && !ctx.author_permissions()?.manage_nicknames() {
+        ctx.say("**Error**: Your nickname has been locked by a moderator!")
+            .await?;
+        return Ok(());
+    }
 
     let to_send = if let Some(nick) = nickname {
         if nick.contains('<') && nick.contains('>') {
             "**Error**: You can't have mentions/emotes in your nickname!"
+        } else if readable_name(&data.regex_cache, &nick).is_none() {
+            "**Error**: Your nickname needs at least one letter or number to be read out!"
         } else {
             tokio::try_join!(
                 data.guilds_db.create_row(guild_id.into()),
@@ -1007,15 +1255,30 @@ pub async fn nick(
             )?;
 
             data.nickname_db
-                .set_one([guild_id.into(), user.id.into()], "name", &nick)
+                .set_one(
+                    [guild_id.into(), user.id.into()],
+                    NicknameColumn::Name,
+                    &nick,
+                )
                 .await?;
 
             &format!("Changed {}'s nickname to {nick}", user.name)
         }
     } else {
-        data.nickname_db
-            .delete([guild_id.into(), user.id.into()])
-            .await?;
+        // Locked nicknames keep their row, so resetting the name does not also unlock it
+        if nickname_row.locked {
+            data.nickname_db
+                .set_one(
+                    [guild_id.into(), user.id.into()],
+                    NicknameColumn::Name,
+                    None::<&str>,
+                )
+                .await?;
+        } else {
+            data.nickname_db
+                .delete([guild_id.into(), user.id.into()])
+                .await?;
+        }
 
         &aformat!("Reset {}'s nickname", &user.name)
     };
@@ -1043,19 +1306,19 @@ pub async fn mode(
     let guild_id = ctx.guild_id().unwrap();
 
     let mode = mode.map(TTSMode::from);
-    let guild_is_premium = data.is_premium_simple(ctx.http(), guild_id).await?;
-    if !can_change_mode(&ctx, mode, guild_is_premium).await? {
+    let premium = data.guild_premium(ctx.http(), guild_id).await?;
+    if !can_change_mode(&ctx, mode, premium).await? {
         return Ok(());
     }
 
-    let key = if guild_is_premium {
-        "premium_voice_mode"
+    let column = if premium.is_some() {
+        UserColumn::PremiumVoiceMode
     } else {
-        "voice_mode"
+        UserColumn::VoiceMode
     };
 
     data.userinfo_db
-        .set_one(ctx.author().id.into(), key, mode)
+        .set_one(ctx.author().id.into(), column, mode)
         .await?;
 
     let response = if let Some(mode) = mode {
@@ -1092,6 +1355,7 @@ pub async fn voice(
         &ctx,
         &data.userinfo_db,
         &data.user_voice_db,
+        UserVoiceColumn::Voice,
         author_id,
         guild_id,
         author_id.into(),
@@ -1125,18 +1389,17 @@ pub async fn translation_languages(ctx: Context<'_>) -> CommandResult {
         )
     };
 
+    let languages = format_languages(data.voices.load().translation_languages.keys());
     ctx.send(
         poise::CreateReply::default().embed(
             CreateEmbed::default()
                 .title(embed_title.as_str())
                 .colour(neutral_colour)
-                .field(
-                    "Currently Supported Languages",
-                    format_languages(data.translation_languages.keys()),
-                    false,
-                )
+                .field("Currently Supported Languages", &languages, false)
                 .author(CreateEmbedAuthor::new(&*author.name).icon_url(author.face()))
                 .footer(CreateEmbedFooter::new(random_footer(
+                    &data.translations,
+                    ctx.language().await?,
                     &data.config.main_server_invite,
                     client_id,
                 ))),
@@ -1160,6 +1423,14 @@ pub async fn voices(
     #[description = "The mode to see the voices for, leave blank for current"] mode: Option<
         TTSModeChoice,
     >,
+    #[description = "Only show voices for this language, such as \"en\" or \"German\""]
+    language: Option<String>,
+    #[description = "Only show voices with this accent or region, such as \"GB\" or \"Australian\""]
+    region: Option<String>,
+    #[description = "Only show voices of this gender"] gender: Option<VoiceGender>,
+    #[description = "Search for voices by name"]
+    #[rest]
+    search: Option<String>,
 ) -> CommandResult {
     let data = ctx.data();
     let http = ctx.http();
@@ -1167,30 +1438,70 @@ pub async fn voices(
     let author = ctx.author();
     let guild_id = ctx.guild_id();
 
-    let mode = match mode {
-        Some(mode) => TTSMode::from(mode),
-        None => data.parse_user_or_guild(http, author.id, guild_id).await?.1,
+    let (current_voice, current_mode) = data.parse_user_or_guild(http, author.id, guild_id).await?;
+    let mode = mode.map(TTSMode::from).unwrap_or(current_mode);
+
+    let entries = voice_entries(data, mode);
+    let recommended_voices = {
+        let guild_locale = ctx.guild().map(|g| g.preferred_locale.to_string());
+        let locale = data.locale(None, guild_id, guild_locale.as_deref()).await?;
+        format_recommended(&recommended(&entries, locale))
+    };
+
+    let filter = VoiceFilter {
+        language,
+        region,
+        gender,
+        search,
     };
 
     let voices = {
-        let run_paginator = |current_voice, pages| async {
-            let footer = random_footer(&data.config.main_server_invite, cache.current_user().id);
+        let run_paginator = |current_voice, pages, recommended| async {
+            let language = ctx.language().await?;
+            let footer = random_footer(
+                &data.translations,
+                language,
+                &data.config.main_server_invite,
+                cache.current_user().id,
+            );
+
+            let paginator = MenuPaginator::new(ctx, language, pages, current_voice, mode, footer)
+                .recommended(recommended);
 
-            let paginator = MenuPaginator::new(ctx, pages, current_voice, mode, footer);
             paginator.start().await?;
             Ok(())
         };
 
+        if !filter.is_empty() {
+            let entries = filter.apply(entries);
+            if entries.is_empty() {
+                let language = ctx.language().await?;
+                ctx.say(data.translations.get(language, "voices.no_matches"))
+                    .await?;
+                return Ok(());
+            }
+
+            let current_voice = if current_mode == mode {
+                &*current_voice
+            } else {
+                mode.default_voice()
+            };
+
+            let current_voice = format_voice(data, current_voice, mode).into_owned();
+            return run_paginator(current_voice, paginate(&entries), None).await;
+        }
+
+        let voice_lists = data.voices.load_full();
         match mode {
-            TTSMode::gTTS => format_languages(data.gtts_voices.keys()),
-            TTSMode::eSpeak => format_languages(data.espeak_voices.iter()),
+            TTSMode::gTTS => format_languages(voice_lists.gtts_voices.keys()),
+            TTSMode::eSpeak => format_languages(voice_lists.espeak_voices.iter()),
             TTSMode::Polly => {
                 let (current_voice, pages) = list_polly_voices(&ctx).await?;
-                return run_paginator(current_voice, pages).await;
+                return run_paginator(current_voice, pages, recommended_voices).await;
             }
             TTSMode::gCloud => {
                 let (current_voice, pages) = list_gcloud_voices(&ctx).await?;
-                return run_paginator(current_voice, pages).await;
+                return run_paginator(current_voice, pages, recommended_voices).await;
             }
         }
     };
@@ -1204,44 +1515,52 @@ pub async fn voices(
         (embed_title, current_user.id)
     };
 
-    ctx.send(
-        poise::CreateReply::default().embed(
-            CreateEmbed::default()
-                .title(embed_title.as_str())
-                .footer(CreateEmbedFooter::new(random_footer(
-                    &data.config.main_server_invite,
-                    client_id,
-                )))
-                .author(CreateEmbedAuthor::new(&*author.name).icon_url(author.face()))
-                .field("Currently supported voices", &voices, true)
-                .field(
-                    "Current voice used",
-                    user_voice_row.voice.as_deref().unwrap_or("None"),
-                    false,
-                ),
-        ),
-    )
-    .await?;
+    let mut embed = CreateEmbed::default()
+        .title(embed_title.as_str())
+        .footer(CreateEmbedFooter::new(random_footer(
+            &data.translations,
+            ctx.language().await?,
+            &data.config.main_server_invite,
+            client_id,
+        )))
+        .author(CreateEmbedAuthor::new(&*author.name).icon_url(author.face()))
+        .field("Currently supported voices", &voices, true);
+
+    if let Some(recommended_voices) = &recommended_voices {
+        embed = embed.field("Recommended for this server", recommended_voices, false);
+    }
+
+    let embed = embed.field(
+        "Current voice used",
+        user_voice_row.voice.as_deref().unwrap_or("None"),
+        false,
+    );
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
     Ok(())
 }
 
-async fn list_polly_voices(ctx: &Context<'_>) -> Result<(String, Vec<String>)> {
+async fn list_polly_voices(ctx: &Context<'_>) -> Result<(String, Vec<VoicePage>)> {
     let data = ctx.data();
 
     let (voice_id, mode) = data
         .parse_user_or_guild(ctx.http(), ctx.author().id, ctx.guild_id())
         .await?;
-    let voice = match mode {
-        TTSMode::Polly => {
-            let voice_id: &str = &voice_id;
-            &data.polly_voices[voice_id]
-        }
-        _ => &data.polly_voices[TTSMode::Polly.default_voice()],
+    let voice_id = match mode {
+        TTSMode::Polly => &voice_id,
+        _ => TTSMode::Polly.default_voice(),
     };
 
+    let voices = data.voices.load();
+    let voice = voices
+        .polly_voices
+        .get(voice_id)
+        .or_else(|| voices.polly_voices.get(TTSMode::Polly.default_voice()))
+        .try_unwrap()?;
+
     let mut lang_to_voices: HashMap<_, Vec<_>> = HashMap::new();
-    for voice in data.polly_voices.values() {
+    for voice in voices.polly_voices.values() {
         lang_to_voices
             .entry(&voice.language_name)
             .or_default()
@@ -1250,19 +1569,18 @@ async fn list_polly_voices(ctx: &Context<'_>) -> Result<(String, Vec<String>)> {
 
     let pages = lang_to_voices
         .into_values()
-        .map(|voices| {
-            let mut buf = String::with_capacity(voices.len() * 12);
-            for voice in voices {
-                writeln!(
-                    buf,
-                    "{} - {} ({})",
-                    voice.id, voice.language_name, voice.gender
-                )?;
-            }
-
-            anyhow::Ok(buf)
+        .flat_map(|voices| {
+            let page_voices: Vec<_> = voices
+                .into_iter()
+                .map(|voice| {
+                    let line = format!("{} - {} ({})", voice.id, voice.language_name, voice.gender);
+                    (voice.id.to_string(), line)
+                })
+                .collect();
+
+            VoicePage::paginate(&page_voices)
         })
-        .collect::<Result<_>>()?;
+        .collect();
 
     Ok((
         format!("{} - {} ({})", voice.id, voice.language_name, voice.gender),
@@ -1270,7 +1588,7 @@ async fn list_polly_voices(ctx: &Context<'_>) -> Result<(String, Vec<String>)> {
     ))
 }
 
-async fn list_gcloud_voices(ctx: &Context<'_>) -> Result<(String, Vec<String>)> {
+async fn list_gcloud_voices(ctx: &Context<'_>) -> Result<(String, Vec<VoicePage>)> {
     let data = ctx.data();
 
     let (lang_variant, mode) = data
@@ -1283,28 +1601,39 @@ async fn list_gcloud_voices(ctx: &Context<'_>) -> Result<(String, Vec<String>)>
     .split_once(' ')
     .unwrap();
 
-    let pages = data
+    let voices = data.voices.load();
+    let pages = voices
         .gcloud_voices
         .iter()
-        .map(|(language, variants)| {
-            let mut buf = String::with_capacity(variants.len() * 12);
-            for (variant, gender) in variants {
-                writeln!(buf, "{language} {variant} ({gender})")?;
-            }
-
-            anyhow::Ok(buf)
+        .flat_map(|(language, variants)| {
+            let page_voices: Vec<_> = variants
+                .iter()
+                .map(|(variant, gender)| {
+                    let line = format!("{language} {variant} ({gender})");
+                    (format!("{language} {variant}"), line)
+                })
+                .collect();
+
+            VoicePage::paginate(&page_voices)
         })
-        .collect::<Result<_>>()?;
+        .collect();
+
+    let gender = voices
+        .gcloud_voices
+        .get(lang)
+        .and_then(|variants| variants.get(variant))
+        .copied()
+        .unwrap_or_default();
 
-    let gender = data.gcloud_voices[lang][variant];
     Ok((format!("{lang} {variant} ({gender})"), pages))
 }
 
-pub fn commands() -> [Command; 5] {
+pub fn commands() -> [Command; 6] {
     [
         settings(),
         setup::setup(),
         voices(),
+        preview::voice_preview(),
         translation_languages(),
         poise::Command {
             subcommands: vec![
@@ -1313,29 +1642,49 @@ pub fn commands() -> [Command; 5] {
                     ..setup::setup()
                 },
                 xsaid(),
+                xsaid_window(),
+                xsaid_reannounce(),
+                xsaid_template::xsaid_template(),
                 autojoin(),
                 required_role(),
                 voice(),
                 server_voice(),
+                language::bot_language(),
+                language::server_bot_language(),
                 mode(),
                 server_mode(),
                 msg_length(),
+                idle_timeout(),
+                idle_leave_message(),
                 botignore(),
                 translation(),
                 translation_lang(),
                 speaking_rate(),
                 nick(),
+                names::nick_lock(),
+                names::name_source(),
                 repeated_characters(),
                 audience_ignore(),
+                stage::stage_reading(),
+                stage_auto_speak(),
+                announce_speakers(),
                 require_voice(),
                 required_prefix(),
                 command_prefix(),
                 text_in_voice(),
                 skip_emoji(),
+                describe_messages(),
+                read_edits(),
+                read_threads(),
+                links::link_reading(),
+                links::link_name(),
                 owner::block(),
                 owner::bot_ban(),
                 owner::gtts_disabled(),
                 use_new_formatting(),
+                voice_announcements::join_announcements(),
+                voice_announcements::leave_announcements(),
+                voice_announcements::announcement_message(),
             ],
             ..set()
         },
@@ -0,0 +1,82 @@
+use poise::serenity_prelude as serenity;
+
+use tts_core::{
+    database_models::{GuildColumn, NicknameColumn},
+    structs::{CommandResult, Context, NameSource},
+    traits::PoiseContextExt as _,
+};
+
+/// Changes which of a member's names is read out, if they have not set a nickname with the bot
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("name_mode", "names")
+)]
+pub async fn name_source(
+    ctx: Context<'_>,
+    #[description = "Which name should be read out"] source: NameSource,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    data.guilds_db
+        .set_one(guild_id.into(), GuildColumn::NameSource, source as i16)
+        .await?;
+
+    let key = match source {
+        NameSource::Nickname => "names.nickname",
+        NameSource::DisplayName => "names.display_name",
+        NameSource::Username => "names.username",
+    };
+
+    let language = ctx.language().await?;
+    ctx.say(data.translations.get(language, key)).await?;
+    Ok(())
+}
+
+/// Stops a member from changing the nickname the bot reads out for them
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "MANAGE_NICKNAMES",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("lock_nick", "nickname_lock")
+)]
+pub async fn nick_lock(
+    ctx: Context<'_>,
+    #[description = "The member to lock the nickname of"] user: serenity::User,
+    #[description = "Whether the member is stopped from changing their nickname"] locked: bool,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    tokio::try_join!(
+        data.guilds_db.create_row(guild_id.into()),
+        data.userinfo_db.create_row(user.id.into())
+    )?;
+
+    data.nickname_db
+        .set_one(
+            [guild_id.into(), user.id.into()],
+            NicknameColumn::Locked,
+            locked,
+        )
+        .await?;
+
+    let key = if locked {
+        "names.locked"
+    } else {
+        "names.unlocked"
+    };
+
+    let language = ctx.language().await?;
+    let msg = data.translations.get(language, key);
+    ctx.say(msg.replace("{user}", &user.name)).await?;
+    Ok(())
+}
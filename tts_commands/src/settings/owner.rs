@@ -3,7 +3,10 @@ use std::sync::atomic::Ordering;
 use aformat::aformat;
 use poise::serenity_prelude as serenity;
 
-use tts_core::structs::{CommandResult, Context};
+use tts_core::{
+    database_models::UserColumn,
+    structs::{CommandResult, Context},
+};
 
 /// Owner only: used to block a user from dms
 #[poise::command(
@@ -16,7 +19,7 @@ use tts_core::structs::{CommandResult, Context};
 pub async fn block(ctx: Context<'_>, user: serenity::UserId, value: bool) -> CommandResult {
     ctx.data()
         .userinfo_db
-        .set_one(user.into(), "dm_blocked", &value)
+        .set_one(user.into(), UserColumn::DmBlocked, &value)
         .await?;
 
     ctx.say("Done!").await?;
@@ -35,11 +38,15 @@ pub async fn bot_ban(ctx: Context<'_>, user: serenity::UserId, value: bool) -> C
     let user_id = user.into();
     let userinfo_db = &ctx.data().userinfo_db;
 
-    userinfo_db.set_one(user_id, "bot_banned", &value).await?;
+    let mut update = userinfo_db
+        .set_many(user_id)
+        .set(UserColumn::BotBanned, value);
     if value {
-        userinfo_db.set_one(user_id, "dm_blocked", &true).await?;
+        update = update.set(UserColumn::DmBlocked, true);
     }
 
+    update.execute().await?;
+
     let msg = aformat!("Set bot ban status for user {user} to `{value}`.");
     ctx.say(msg.as_str()).await?;
 
@@ -0,0 +1,143 @@
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::small_fixed_array::FixedString;
+
+use tts_core::{
+    common::{audio_to_input, fetch_audio, prepare_url},
+    structs::{CommandResult, Context, Result, TTSMode},
+    traits::PoiseContextExt as _,
+};
+
+use super::check_valid_voice;
+
+pub(super) const DEFAULT_PREVIEW_TEXT: &str = "Hello! This is what I sound like with this voice.";
+const MAX_PREVIEW_LENGTH: usize = 200;
+const MAX_PREVIEW_SECONDS: &str = "15";
+
+/// Returns the voice channel the author and bot are both in, so the preview can be played there.
+fn shared_voice_channel(ctx: Context<'_>) -> Option<serenity::ChannelId> {
+    let author_vc = ctx.author_vc()?;
+    let bot_id = ctx.cache().current_user().id;
+    let bot_vc = ctx.guild()?.voice_states.get(&bot_id)?.channel_id?;
+
+    (author_vc == bot_vc).then_some(author_vc)
+}
+
+/// Reads out a sample of a voice, in the voice channel if the author is there with the bot,
+/// otherwise sending the audio as a file.
+pub(super) async fn send_preview(
+    ctx: Context<'_>,
+    voice: &str,
+    mode: TTSMode,
+    text: &str,
+) -> Result<()> {
+    let data = ctx.data();
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    if mode.is_premium() {
+        let premium = match ctx.guild_id() {
+            Some(guild_id) => data.guild_premium(ctx.http(), guild_id).await?,
+            None => None,
+        };
+
+        if !mode.is_unlocked(premium) {
+            let msg = t("preview.requires_premium").replace("{mode}", mode.into());
+            ctx.say(msg).await?;
+            return Ok(());
+        }
+    }
+
+    let speaking_rate = mode
+        .speaking_rate_info()
+        .map(|info| info.default)
+        .unwrap_or("1.0");
+
+    let url = prepare_url(
+        data.config.tts_service.clone(),
+        text,
+        voice,
+        mode,
+        speaking_rate,
+        MAX_PREVIEW_SECONDS,
+        None,
+    );
+
+    let Some(audio) = fetch_audio(&data, url, mode).await? else {
+        ctx.say(t("preview.failed")).await?;
+        return Ok(());
+    };
+
+    if let Some(channel_id) = shared_voice_channel(ctx)
+        && let Some(guild_id) = ctx.guild_id()
+        && let Some(call_lock) = data.songbird.get(guild_id)
+    {
+        let input = audio_to_input(audio)?;
+        call_lock.lock().await.enqueue_input(input).await;
+
+        let msg = t("preview.playing")
+            .replace("{voice}", voice)
+            .replace("{channel_id}", &channel_id.to_string());
+
+        ctx.say(msg).await?;
+    } else {
+        let extension = match mode {
+            TTSMode::gTTS | TTSMode::gCloud | TTSMode::Polly => "mp3",
+            TTSMode::eSpeak => "wav",
+        };
+
+        let file_name = format!("preview-{}.{extension}", ctx.id());
+        let attachment =
+            serenity::CreateAttachment::bytes(audio.bytes().await?.to_vec(), file_name);
+
+        let content = t("preview.attached").replace("{voice}", voice);
+        let reply = CreateReply::default()
+            .content(content)
+            .attachment(attachment);
+
+        ctx.send(reply).await?;
+    }
+
+    Ok(())
+}
+
+/// Lets you hear a voice before changing to it with `/set voice`
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_bot_permissions = "SEND_MESSAGES | ATTACH_FILES",
+    aliases("preview", "preview_voice")
+)]
+pub async fn voice_preview(
+    ctx: Context<'_>,
+    #[description = "The voice to preview"]
+    #[autocomplete = "super::voice_autocomplete"]
+    voice: FixedString<u8>,
+    #[description = "The text to read, leave blank for a sample sentence"]
+    #[rest]
+    text: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let (_, mode) = data
+        .parse_user_or_guild(ctx.http(), ctx.author().id, ctx.guild_id())
+        .await?;
+
+    let language = ctx.language().await?;
+    let t = |key: &'static str| data.translations.get(language, key);
+
+    if !check_valid_voice(&data, &voice, mode) {
+        let msg = t("preview.invalid_voice").replace("{mode}", mode.into());
+        ctx.say(msg).await?;
+        return Ok(());
+    }
+
+    let text = text.as_deref().unwrap_or(DEFAULT_PREVIEW_TEXT);
+    if text.chars().count() > MAX_PREVIEW_LENGTH {
+        let msg = t("preview.too_long").replace("{max_length}", &MAX_PREVIEW_LENGTH.to_string());
+        ctx.say(msg).await?;
+        return Ok(());
+    }
+
+    send_preview(ctx, &voice, mode, text).await
+}
@@ -12,6 +12,7 @@ use tts_core::{
     opt_ext::OptionTryUnwrap as _,
     require, require_guild,
     structs::{CommandResult, Context, Result},
+    traits::PoiseContextExt as _,
 };
 
 fn can_send_generic(permissions: Permissions) -> bool {
@@ -42,7 +43,19 @@ struct ChannelMenuEntry {
     id_str: <u64 as ToArrayString>::ArrayString,
     name: FixedString<u16>,
     position: u16,
-    has_webhook_perms: bool,
+    can_follow_announcements: bool,
+}
+
+/// Announcements can only be followed into text channels, and need Manage Webhooks to do so.
+fn can_follow_announcements(
+    guild: &serenity::Guild,
+    channel: &serenity::GuildChannel,
+    bot_member: &serenity::Member,
+) -> bool {
+    channel.kind != serenity::ChannelType::Forum
+        && guild
+            .user_permissions_in(channel, bot_member)
+            .manage_webhooks()
 }
 
 fn get_eligible_channels(
@@ -65,8 +78,10 @@ fn get_eligible_channels(
         .channels
         .iter()
         .filter(|c| {
-            c.kind == serenity::ChannelType::Text
-                && can_send(&guild, c, bot_member)
+            matches!(
+                c.kind,
+                serenity::ChannelType::Text | serenity::ChannelType::Forum
+            ) && can_send(&guild, c, bot_member)
                 && author_can_send(c)
         })
         .map(|c| ChannelMenuEntry {
@@ -74,7 +89,7 @@ fn get_eligible_channels(
             id_str: c.id.to_arraystring(),
             name: c.name.clone(),
             position: c.position,
-            has_webhook_perms: guild.user_permissions_in(c, bot_member).manage_webhooks(),
+            can_follow_announcements: can_follow_announcements(&guild, c, bot_member),
         })
         .collect();
 
@@ -129,7 +144,7 @@ async fn show_channel_select_menu(
         .find(|entry| entry.id == selected_id)
         .unwrap();
 
-    Ok(Some((selected_id, selected_entry.has_webhook_perms)))
+    Ok(Some((selected_id, selected_entry.can_follow_announcements)))
 }
 
 fn generate_channel_select(text_channels: &[ChannelMenuEntry]) -> Vec<CreateActionRow<'_>> {
@@ -163,7 +178,7 @@ fn generate_channel_select(text_channels: &[ChannelMenuEntry]) -> Vec<CreateActi
 pub async fn setup(
     ctx: Context<'_>,
     #[description = "The channel for the bot to read messages from"]
-    #[channel_types("Text")]
+    #[channel_types("Text", "Forum")]
     channel: Option<serenity::GuildChannel>,
 ) -> CommandResult {
     let data = ctx.data();
@@ -179,20 +194,27 @@ pub async fn setup(
         )
     };
 
-    let (channel_id, has_webhook_perms) = {
+    let (channel_id, can_follow_announcements) = {
         let bot_member = guild_id.member(ctx, bot_user_id).await?;
-        let (channel, has_webhook_perms) = if let Some(channel) = channel {
-            let chan_perms = require_guild!(ctx).user_permissions_in(&channel, &bot_member);
-            (channel.id, chan_perms.manage_webhooks())
+        let (channel, can_follow_announcements) = if let Some(channel) = channel {
+            let guild = require_guild!(ctx);
+            (
+                channel.id,
+                can_follow_announcements(&guild, &channel, &bot_member),
+            )
         } else {
             require!(show_channel_select_menu(ctx, &bot_member).await?, Ok(()))
         };
 
-        (channel, has_webhook_perms)
+        (channel, can_follow_announcements)
     };
 
     data.guilds_db
-        .set_one(guild_id.into(), "channel", &(channel_id.get() as i64))
+        .set_one(
+            guild_id.into(),
+            GuildColumn::Channel,
+            &(channel_id.get() as i64),
+        )
         .await?;
     ctx.send(
         poise::CreateReply::default().embed(
@@ -207,6 +229,8 @@ pub async fn setup(
                     .as_str(),
                 )
                 .footer(serenity::CreateEmbedFooter::new(random_footer(
+                    &data.translations,
+                    ctx.language().await?,
                     &data.config.main_server_invite,
                     bot_user_id,
                 )))
@@ -219,7 +243,7 @@ pub async fn setup(
         return Ok(());
     };
 
-    if !has_webhook_perms {
+    if !can_follow_announcements {
         return Ok(());
     }
 
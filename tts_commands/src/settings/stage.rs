@@ -0,0 +1,37 @@
+use tts_core::{
+    database_models::GuildColumn,
+    structs::{CommandResult, Context, StageReadingMode},
+    traits::PoiseContextExt as _,
+};
+
+/// Changes who is read out when in a stage channel
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("stage_mode", "stage_read")
+)]
+pub async fn stage_reading(
+    ctx: Context<'_>,
+    #[description = "Who should be read out in stage channels"] mode: StageReadingMode,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    data.guilds_db
+        .set_one(guild_id.into(), GuildColumn::StageReading, mode as i16)
+        .await?;
+
+    let key = match mode {
+        StageReadingMode::Everyone => "stage.everyone",
+        StageReadingMode::Speakers => "stage.speakers",
+        StageReadingMode::Moderators => "stage.moderators",
+    };
+
+    let language = ctx.language().await?;
+    ctx.say(data.translations.get(language, key)).await?;
+    Ok(())
+}
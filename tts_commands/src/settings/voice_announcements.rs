@@ -0,0 +1,115 @@
+use aformat::aformat;
+
+use tts_core::{
+    database_models::VoiceAnnouncementColumn,
+    structs::{CommandResult, Context, VoiceAnnouncementKind},
+};
+
+use super::{bool_button, replace_bool};
+
+const MAX_MESSAGE_LENGTH: usize = 100;
+
+async fn generic_announcement_bool(
+    ctx: Context<'_>,
+    column: VoiceAnnouncementColumn,
+    value: Option<bool>,
+    resp: &'static str,
+) -> CommandResult {
+    let Some(value) = bool_button(ctx, value).await? else {
+        return Ok(());
+    };
+
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    data.guilds_db.create_row(guild_id.into()).await?;
+    data.voice_announcements_db
+        .set_one(guild_id.into(), column, value)
+        .await?;
+
+    ctx.say(replace_bool(resp, value)).await?;
+    Ok(())
+}
+
+/// Makes the bot say when members join or move into its voice channel
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("announce_joins", "join_announcement")
+)]
+pub async fn join_announcements(
+    ctx: Context<'_>,
+    #[description = "True or False?"] value: Option<bool>,
+) -> CommandResult {
+    let resp = "Announcing members joining the voice channel is now {}.";
+    generic_announcement_bool(ctx, VoiceAnnouncementColumn::AnnounceJoins, value, resp).await
+}
+
+/// Makes the bot say when members leave its voice channel
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("announce_leaves", "leave_announcement")
+)]
+pub async fn leave_announcements(
+    ctx: Context<'_>,
+    #[description = "True or False?"] value: Option<bool>,
+) -> CommandResult {
+    let resp = "Announcing members leaving the voice channel is now {}.";
+    generic_announcement_bool(ctx, VoiceAnnouncementColumn::AnnounceLeaves, value, resp).await
+}
+
+/// Changes what the bot says when members join, leave, or move into its voice channel
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("announcement_template")
+)]
+pub async fn announcement_message(
+    ctx: Context<'_>,
+    #[description = "The announcement to change"] kind: VoiceAnnouncementKind,
+    #[description = "The message to say, {name} is replaced with the member's name. Leave blank to reset"]
+    #[rest]
+    message: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    let to_send: &str = if let Some(message) = message {
+        if message.chars().count() > MAX_MESSAGE_LENGTH {
+            &aformat!("**Error**: Announcement messages cannot be longer than {MAX_MESSAGE_LENGTH} characters")
+        } else {
+            data.guilds_db.create_row(guild_id.into()).await?;
+            data.voice_announcements_db
+                .set_one(guild_id.into(), kind.column(), &message)
+                .await?;
+
+            &format!("The announcement message is now: `{message}`")
+        }
+    } else {
+        data.guilds_db.create_row(guild_id.into()).await?;
+        data.voice_announcements_db
+            .set_one(guild_id.into(), kind.column(), None::<String>)
+            .await?;
+
+        &format!(
+            "Reset the announcement message to: `{}`",
+            kind.default_message()
+        )
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
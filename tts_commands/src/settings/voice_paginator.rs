@@ -1,45 +1,116 @@
 use std::borrow::Cow;
 
 use poise::serenity_prelude as serenity;
-use serenity::{builder::*, small_fixed_array::FixedString, CollectComponentInteractions};
+use serenity::{
+    builder::*, small_fixed_array::FixedString, CollectComponentInteractions,
+    ComponentInteractionDataKind,
+};
 
-use tts_core::structs::{Context, TTSMode};
+use tts_core::structs::{Context, Data, Result, TTSMode};
+
+use super::preview::{send_preview, DEFAULT_PREVIEW_TEXT};
+
+/// The most options Discord allows in a select menu, and so the most voices on a page.
+const MAX_PAGE_VOICES: usize = 25;
+
+/// A page of voices, along with the IDs of the voices on it for the preview menu.
+pub struct VoicePage {
+    pub description: String,
+    pub voices: Vec<(String, String)>,
+}
+
+impl VoicePage {
+    /// Splits `(id, label)` pairs into pages small enough to all fit in the preview menu.
+    pub fn paginate(voices: &[(String, String)]) -> Vec<Self> {
+        voices
+            .chunks(MAX_PAGE_VOICES)
+            .map(|voices| {
+                let mut description = String::new();
+                for (_, label) in voices {
+                    description.push_str(label);
+                    description.push('\n');
+                }
+
+                Self {
+                    description,
+                    voices: voices.to_vec(),
+                }
+            })
+            .collect()
+    }
+}
 
 pub struct MenuPaginator<'a> {
     index: usize,
     mode: TTSMode,
     ctx: Context<'a>,
-    pages: Vec<String>,
+    language: &'a str,
+    pages: Vec<VoicePage>,
     footer: Cow<'a, str>,
     current_voice: String,
+    recommended: Option<String>,
 }
 
 impl<'a> MenuPaginator<'a> {
     pub fn new(
         ctx: Context<'a>,
-        pages: Vec<String>,
+        language: &'a str,
+        mut pages: Vec<VoicePage>,
         current_voice: String,
         mode: TTSMode,
         footer: Cow<'a, str>,
     ) -> Self {
+        // If no voices could be loaded, show an empty page instead of having none to index.
+        if pages.is_empty() {
+            pages.push(VoicePage {
+                description: String::new(),
+                voices: Vec::new(),
+            });
+        }
+
         Self {
             ctx,
+            language,
             pages,
             current_voice,
             mode,
             footer,
             index: 0,
+            recommended: None,
         }
     }
 
-    fn create_page(&self, page: &str) -> CreateEmbed<'_> {
+    pub fn recommended(mut self, recommended: Option<String>) -> Self {
+        self.recommended = recommended;
+        self
+    }
+
+    fn t(&self, key: &'static str) -> &'a str {
+        let data = self.ctx.serenity_context().data_ref::<Data>();
+        data.translations.get(self.language, key)
+    }
+
+    fn create_page(&self, page: &VoicePage) -> CreateEmbed<'_> {
         let author = self.ctx.author();
         let bot_user = &self.ctx.cache().current_user().name;
 
-        CreateEmbed::default()
-            .title(format!("{bot_user} Voices | Mode: `{}`", self.mode))
-            .description(format!("**Currently Supported Voice**\n{page}"))
-            .field("Current voice used", &self.current_voice, false)
+        let title = self
+            .t("voices.title")
+            .replace("{bot_name}", bot_user)
+            .replace("{mode}", self.mode.into());
+
+        let mut embed = CreateEmbed::default().title(title).description(format!(
+            "{}\n{}",
+            self.t("voices.supported"),
+            page.description
+        ));
+
+        if let Some(recommended) = &self.recommended {
+            embed = embed.field(self.t("voices.recommended"), recommended, false);
+        }
+
+        embed
+            .field(self.t("voices.current"), &self.current_voice, false)
             .author(CreateEmbedAuthor::new(&*author.name).icon_url(author.face()))
             .footer(CreateEmbedFooter::new(self.footer.as_ref()))
     }
@@ -65,8 +136,32 @@ impl<'a> MenuPaginator<'a> {
         serenity::CreateActionRow::Buttons(buttons)
     }
 
+    fn create_preview_menu(&self, disabled: bool) -> Option<serenity::CreateActionRow<'_>> {
+        let voices = &self.pages[self.index].voices;
+        if voices.is_empty() {
+            return None;
+        }
+
+        let options = voices
+            .iter()
+            .map(|(id, label)| CreateSelectMenuOption::new(label.as_str(), id.as_str()))
+            .collect();
+
+        let menu = CreateSelectMenu::new("preview", CreateSelectMenuKind::String { options })
+            .placeholder(self.t("voices.preview_placeholder"))
+            .disabled(disabled);
+
+        Some(serenity::CreateActionRow::SelectMenu(menu))
+    }
+
+    fn create_components(&self, disabled: bool) -> Vec<serenity::CreateActionRow<'_>> {
+        let mut components = vec![self.create_action_row(disabled)];
+        components.extend(self.create_preview_menu(disabled));
+        components
+    }
+
     async fn create_message(&self) -> serenity::Result<serenity::MessageId> {
-        let components = [self.create_action_row(false)];
+        let components = self.create_components(false);
         let builder = poise::CreateReply::default()
             .embed(self.create_page(&self.pages[self.index]))
             .components(&components);
@@ -82,7 +177,7 @@ impl<'a> MenuPaginator<'a> {
         let http = self.ctx.http();
         let channel_id = self.ctx.channel_id();
 
-        let components = [self.create_action_row(disable)];
+        let components = self.create_components(disable);
         let builder = EditMessage::default()
             .embed(self.create_page(&self.pages[self.index]))
             .components(&components);
@@ -90,7 +185,7 @@ impl<'a> MenuPaginator<'a> {
         Ok(channel_id.edit_message(http, message, builder).await?.id)
     }
 
-    pub async fn start(mut self) -> serenity::Result<()> {
+    pub async fn start(mut self) -> Result<()> {
         let mut message_id = self.create_message().await?;
         let serenity_context = self.ctx.serenity_context();
 
@@ -115,7 +210,19 @@ impl<'a> MenuPaginator<'a> {
                 }
                 "⏹️" => {
                     self.edit_message(message_id, true).await?;
-                    return interaction.defer(&serenity_context.http).await;
+                    interaction.defer(&serenity_context.http).await?;
+                    return Ok(());
+                }
+                "preview" => {
+                    interaction.defer(&serenity_context.http).await?;
+                    if let ComponentInteractionDataKind::StringSelect { values } =
+                        &interaction.data.kind
+                        && let Some(voice) = values.first()
+                    {
+                        send_preview(self.ctx, voice, self.mode, DEFAULT_PREVIEW_TEXT).await?;
+                    }
+
+                    continue;
                 }
                 "▶️" => {
                     self.index += 1;
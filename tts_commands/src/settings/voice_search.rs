@@ -0,0 +1,273 @@
+use std::fmt::Write;
+
+use tts_core::structs::{Data, GoogleGender, PollyGender, TTSMode};
+
+use super::voice_paginator::VoicePage;
+
+const MAX_RECOMMENDED: usize = 8;
+
+#[derive(poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceGender {
+    Male,
+    Female,
+}
+
+impl VoiceGender {
+    fn from_google(gender: GoogleGender) -> Option<Self> {
+        match gender {
+            GoogleGender::Male => Some(Self::Male),
+            GoogleGender::Female => Some(Self::Female),
+            GoogleGender::Unspecified => None,
+        }
+    }
+
+    fn from_polly(gender: PollyGender) -> Self {
+        match gender {
+            PollyGender::Male => Self::Male,
+            PollyGender::Female => Self::Female,
+        }
+    }
+}
+
+/// A voice of any mode, flattened so it can be filtered and searched uniformly.
+pub struct VoiceEntry {
+    pub id: String,
+    pub label: String,
+    language_code: Option<String>,
+    language_name: Option<String>,
+    gender: Option<VoiceGender>,
+}
+
+impl VoiceEntry {
+    fn primary_language(&self) -> Option<&str> {
+        let code = self.language_code.as_deref()?;
+        Some(code.split(['-', '_']).next().unwrap_or(code))
+    }
+
+    fn region(&self) -> Option<&str> {
+        let code = self.language_code.as_deref()?;
+        code.split_once(['-', '_']).map(|(_, region)| region)
+    }
+}
+
+pub fn voice_entries(data: &Data, mode: TTSMode) -> Vec<VoiceEntry> {
+    let voices = data.voices.load();
+    match mode {
+        TTSMode::gTTS => voices
+            .gtts_voices
+            .iter()
+            .map(|(code, name)| VoiceEntry {
+                id: code.to_string(),
+                label: format!("{name} ({code})"),
+                language_code: Some(code.to_string()),
+                language_name: Some(name.to_string()),
+                gender: None,
+            })
+            .collect(),
+        TTSMode::eSpeak => voices
+            .espeak_voices
+            .iter()
+            .map(|voice| VoiceEntry {
+                id: voice.to_string(),
+                label: voice.to_string(),
+                language_code: None,
+                language_name: None,
+                gender: None,
+            })
+            .collect(),
+        TTSMode::Polly => voices
+            .polly_voices
+            .values()
+            .map(|voice| VoiceEntry {
+                id: voice.id.to_string(),
+                label: format!(
+                    "{} - {} ({})",
+                    voice.name, voice.language_name, voice.gender
+                ),
+                language_code: Some(voice.language_code.to_string()),
+                language_name: Some(voice.language_name.to_string()),
+                gender: Some(VoiceGender::from_polly(voice.gender)),
+            })
+            .collect(),
+        TTSMode::gCloud => voices
+            .gcloud_voices
+            .iter()
+            .flat_map(|(language, variants)| {
+                variants.iter().map(move |(variant, gender)| VoiceEntry {
+                    id: format!("{language} {variant}"),
+                    label: format!("{language} {variant} ({gender})"),
+                    language_code: Some(language.to_string()),
+                    language_name: None,
+                    gender: VoiceGender::from_google(*gender),
+                })
+            })
+            .collect(),
+    }
+}
+
+#[derive(Default)]
+pub struct VoiceFilter {
+    pub language: Option<String>,
+    pub region: Option<String>,
+    pub gender: Option<VoiceGender>,
+    pub search: Option<String>,
+}
+
+impl VoiceFilter {
+    /// Parses free text from autocomplete, pulling out genders and language codes
+    /// known to `entries`, and leaving the rest as a name search.
+    pub fn parse(entries: &[VoiceEntry], input: &str) -> Self {
+        let mut filter = Self::default();
+        let mut search = Vec::new();
+
+        for word in input.split_whitespace() {
+            let word_lower = word.to_lowercase();
+            if filter.gender.is_none() && matches!(&*word_lower, "male" | "female") {
+                filter.gender = Some(if word_lower == "male" {
+                    VoiceGender::Male
+                } else {
+                    VoiceGender::Female
+                });
+            } else if filter.language.is_none()
+                && word_lower.len() >= 2
+                && entries.iter().any(|e| {
+                    e.language_code
+                        .as_deref()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(&word_lower))
+                        || e.primary_language()
+                            .is_some_and(|l| l.eq_ignore_ascii_case(&word_lower))
+                })
+            {
+                filter.language = Some(word_lower);
+            } else {
+                search.push(word);
+            }
+        }
+
+        if !search.is_empty() {
+            filter.search = Some(search.join(" "));
+        }
+
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.language.is_none()
+            && self.region.is_none()
+            && self.gender.is_none()
+            && self.search.is_none()
+    }
+
+    fn matches(&self, entry: &VoiceEntry) -> bool {
+        if let Some(gender) = self.gender
+            && entry.gender != Some(gender)
+        {
+            return false;
+        }
+
+        if let Some(language) = &self.language {
+            let language = language.to_lowercase();
+            let code_matches = entry.language_code.as_deref().is_some_and(|code| {
+                code.eq_ignore_ascii_case(&language)
+                    || entry
+                        .primary_language()
+                        .is_some_and(|l| l.eq_ignore_ascii_case(&language))
+            });
+            let name_matches = entry
+                .language_name
+                .as_deref()
+                .is_some_and(|name| name.to_lowercase().contains(&language));
+
+            if !code_matches && !name_matches {
+                return false;
+            }
+        }
+
+        if let Some(region) = &self.region {
+            let region = region.to_lowercase();
+            let code_matches = entry
+                .region()
+                .is_some_and(|r| r.eq_ignore_ascii_case(&region));
+            let name_matches = entry
+                .language_name
+                .as_deref()
+                .is_some_and(|name| name.to_lowercase().contains(&region));
+
+            if !code_matches && !name_matches {
+                return false;
+            }
+        }
+
+        if let Some(search) = &self.search {
+            return fuzzy_matches(&entry.label.to_lowercase(), &search.to_lowercase());
+        }
+
+        true
+    }
+
+    /// Filters the entries, then orders them by how closely they match the search.
+    pub fn apply(&self, entries: Vec<VoiceEntry>) -> Vec<VoiceEntry> {
+        let mut entries: Vec<_> = entries.into_iter().filter(|e| self.matches(e)).collect();
+        if let Some(search) = &self.search {
+            rank_by_search(&mut entries, search);
+        }
+
+        entries
+    }
+}
+
+fn fuzzy_matches(label_lower: &str, search_lower: &str) -> bool {
+    label_lower.contains(search_lower)
+        || label_lower
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| strsim::normalized_damerau_levenshtein(word, search_lower) >= 0.7)
+}
+
+pub fn rank_by_search(entries: &mut [VoiceEntry], search: &str) {
+    let search_lower = search.to_lowercase();
+
+    entries.sort_by_cached_key(|entry| {
+        let label_lower = entry.label.to_lowercase();
+        let distance = strsim::levenshtein(&label_lower, &search_lower);
+        (!label_lower.contains(&search_lower), distance)
+    });
+}
+
+/// Picks voices matching the server's language, preferring those with the same region.
+pub fn recommended<'a>(entries: &'a [VoiceEntry], locale: &str) -> Vec<&'a VoiceEntry> {
+    let (language, region) = locale.split_once('-').unwrap_or((locale, ""));
+
+    let mut recommended: Vec<_> = entries
+        .iter()
+        .filter(|e| {
+            e.primary_language()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        })
+        .collect();
+
+    recommended.sort_by_key(|e| !e.region().is_some_and(|r| r.eq_ignore_ascii_case(region)));
+    recommended.truncate(MAX_RECOMMENDED);
+    recommended
+}
+
+pub fn format_recommended(recommended: &[&VoiceEntry]) -> Option<String> {
+    if recommended.is_empty() {
+        return None;
+    }
+
+    let mut buf = String::new();
+    for entry in recommended {
+        writeln!(buf, "{}", entry.label).ok()?;
+    }
+
+    Some(buf)
+}
+
+pub fn paginate(entries: &[VoiceEntry]) -> Vec<VoicePage> {
+    let voices: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.id.clone(), entry.label.clone()))
+        .collect();
+
+    VoicePage::paginate(&voices)
+}
@@ -0,0 +1,55 @@
+use tts_core::{
+    database_models::GuildColumn,
+    structs::{CommandResult, Context},
+    xsaid_template::{TemplateValues, XsaidTemplate},
+};
+
+/// Changes how messages are read, such as `{name} said {message}`
+#[poise::command(
+    guild_only,
+    category = "Settings",
+    prefix_command,
+    slash_command,
+    required_permissions = "ADMINISTRATOR",
+    required_bot_permissions = "SEND_MESSAGES",
+    aliases("xsaid_format", "message_template")
+)]
+pub async fn xsaid_template(
+    ctx: Context<'_>,
+    #[description = "Uses {name}, {message}, {link}, {attachment}, {reply}, and [optional parts]. Leave blank to reset"]
+    #[rest]
+    template: Option<String>,
+) -> CommandResult {
+    let data = ctx.data();
+    let guild_id = ctx.guild_id().unwrap();
+
+    let to_send = if let Some(template) = template {
+        match XsaidTemplate::parse(&template) {
+            Ok(parsed) => {
+                data.guilds_db
+                    .set_one(guild_id.into(), GuildColumn::XsaidTemplate, &template)
+                    .await?;
+
+                let example = parsed.render(&TemplateValues {
+                    name: Some("Alice"),
+                    message: Some("hello"),
+                    link: Some("a link"),
+                    reply: Some("Bob"),
+                    attachment: None,
+                });
+
+                format!("Messages will now be read like: `{example}`")
+            }
+            Err(err) => format!("**Error**: {err}"),
+        }
+    } else {
+        data.guilds_db
+            .set_one(guild_id.into(), GuildColumn::XsaidTemplate, &None::<String>)
+            .await?;
+
+        String::from("Messages will now be read with the default phrasing.")
+    };
+
+    ctx.say(to_send).await?;
+    Ok(())
+}
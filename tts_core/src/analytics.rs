@@ -10,12 +10,12 @@ bool_enum!(EventType(Normal | Command));
 
 pub struct Handler {
     pub log_buffer: DashMap<(Cow<'static, str>, EventType), i32>,
-    pub pool: sqlx::PgPool,
+    pub pool: crate::storage::Pool,
 }
 
 impl Handler {
     #[must_use]
-    pub fn new(pool: sqlx::PgPool) -> Self {
+    pub fn new(pool: crate::storage::Pool) -> Self {
         Self {
             pool,
             log_buffer: DashMap::new(),
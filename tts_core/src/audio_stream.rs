@@ -0,0 +1,100 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use bytes::Bytes;
+use songbird::input::{AsyncMediaSource, AudioStreamError};
+use tokio::{
+    io::{AsyncRead, AsyncSeek, ReadBuf},
+    sync::mpsc,
+};
+
+/// How many chunks may be downloaded ahead of playback.
+const CHUNK_BUFFER: usize = 16;
+
+/// Forwards a response body to songbird as it downloads, so playback can begin before it has finished.
+pub struct ResponseStream {
+    chunks: mpsc::Receiver<io::Result<Bytes>>,
+    current: Bytes,
+    length: Option<u64>,
+}
+
+impl ResponseStream {
+    #[must_use]
+    pub fn new(mut response: reqwest::Response) -> Self {
+        let length = response.content_length();
+        let (sender, chunks) = mpsc::channel(CHUNK_BUFFER);
+
+        tokio::spawn(async move {
+            loop {
+                let chunk = match response.chunk().await {
+                    Ok(Some(chunk)) => Ok(chunk),
+                    Ok(None) => break,
+                    Err(err) => Err(io::Error::other(err)),
+                };
+
+                // Stop downloading if the track has been dropped, or the download failed.
+                let is_err = chunk.is_err();
+                if sender.send(chunk).await.is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            chunks,
+            length,
+            current: Bytes::new(),
+        }
+    }
+}
+
+impl AsyncRead for ResponseStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.current.is_empty() {
+            match ready!(self.chunks.poll_recv(cx)) {
+                Some(Ok(chunk)) => self.current = chunk,
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        let len = buf.remaining().min(self.current.len());
+        buf.put_slice(&self.current.split_to(len));
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for ResponseStream {
+    fn start_seek(self: Pin<&mut Self>, _position: io::SeekFrom) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Err(io::ErrorKind::Unsupported.into()))
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncMediaSource for ResponseStream {
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
+    async fn byte_len(&self) -> Option<u64> {
+        self.length
+    }
+
+    async fn try_resume(
+        &mut self,
+        _offset: u64,
+    ) -> Result<Box<dyn AsyncMediaSource>, AudioStreamError> {
+        Err(AudioStreamError::Unsupported)
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use tracing::{info, warn};
+
+use poise::serenity_prelude::GuildId;
+
+use crate::structs::TTSMode;
+
+/// How far back results are considered when deciding whether to open a circuit.
+const WINDOW: Duration = Duration::from_secs(60);
+/// The minimum number of failures within [`WINDOW`] before a circuit can open.
+const MIN_FAILURES: usize = 5;
+/// How long an open circuit waits before letting a single probe request through.
+const OPEN_DURATION: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct Breaker {
+    results: VecDeque<(Instant, bool)>,
+    /// If set, the circuit is open and no requests are allowed until this time.
+    open_until: Option<Instant>,
+    /// If set, a probe has been let through and no others are allowed until this time.
+    probe_until: Option<Instant>,
+    /// Guilds that have been told about the current outage.
+    notified: HashSet<GuildId>,
+}
+
+impl Breaker {
+    fn prune(&mut self, now: Instant) {
+        while let Some((at, _)) = self.results.front()
+            && now.duration_since(*at) > WINDOW
+        {
+            self.results.pop_front();
+        }
+    }
+
+    /// Opens once at least half of the recent requests have failed.
+    fn should_open(&self) -> bool {
+        let failures = self.results.iter().filter(|(_, ok)| !ok).count();
+        failures >= MIN_FAILURES && failures * 2 >= self.results.len()
+    }
+}
+
+/// Tracks the health of each [`TTSMode`], so a failing mode can be swapped for a fallback
+/// instead of erroring on every message.
+#[derive(Default)]
+pub struct CircuitBreakers {
+    breakers: [Mutex<Breaker>; 4],
+}
+
+impl CircuitBreakers {
+    fn get(&self, mode: TTSMode) -> &Mutex<Breaker> {
+        &self.breakers[match mode {
+            TTSMode::gTTS => 0,
+            TTSMode::Polly => 1,
+            TTSMode::eSpeak => 2,
+            TTSMode::gCloud => 3,
+        }]
+    }
+
+    /// Checks if a request may be made with this mode.
+    ///
+    /// Once an open circuit has waited long enough, this lets a single probe through
+    /// (half-open) and keeps the circuit open for everything else until the probe is recorded.
+    pub fn allow(&self, mode: TTSMode) -> bool {
+        let mut breaker = self.get(mode).lock();
+        let Some(open_until) = breaker.open_until else {
+            return true;
+        };
+
+        let now = Instant::now();
+        if now < open_until || breaker.probe_until.is_some_and(|until| now < until) {
+            return false;
+        }
+
+        breaker.probe_until = Some(now + OPEN_DURATION);
+        true
+    }
+
+    /// Checks if the circuit is open, without letting a probe through like [`Self::allow`].
+    ///
+    /// Once an open circuit has waited long enough, this is false so the probe can be made.
+    pub fn is_open(&self, mode: TTSMode) -> bool {
+        let breaker = self.get(mode).lock();
+        breaker
+            .open_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    pub fn record(&self, mode: TTSMode, success: bool) {
+        let mut breaker = self.get(mode).lock();
+        let now = Instant::now();
+
+        if breaker.open_until.is_some() {
+            if success {
+                info!("Circuit breaker: {mode} has recovered, closing circuit");
+                *breaker = Breaker::default();
+            } else {
+                breaker.open_until = Some(now + OPEN_DURATION);
+                breaker.probe_until = None;
+            }
+
+            return;
+        }
+
+        breaker.prune(now);
+        breaker.results.push_back((now, success));
+        if !success && breaker.should_open() {
+            warn!("Circuit breaker: {mode} is failing, opening circuit");
+            breaker.open_until = Some(now + OPEN_DURATION);
+            breaker.results.clear();
+        }
+    }
+
+    /// Returns true the first time this is called for a guild during an outage of `mode`.
+    pub fn should_notify(&self, mode: TTSMode, guild_id: GuildId) -> bool {
+        let mut breaker = self.get(mode).lock();
+        breaker.open_until.is_some() && breaker.notified.insert(guild_id)
+    }
+}
@@ -1,15 +1,30 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::num::NonZeroU8;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
+use aformat::ToArrayString as _;
 use itertools::Itertools;
 use rand::Rng as _;
 
 use serenity::all as serenity;
 use serenity::{CollectComponentInteractions, CreateActionRow, CreateButton};
 
+use crate::audio_stream::ResponseStream;
+use crate::constants::{
+    AUDIO_STREAM_BUFFER, DEFAULT_TTS_MAX_RETRIES, GENERIC_FILENAMES, KNOWN_SITES,
+    MAX_FILENAME_LENGTH, TTS_RETRY_BASE_DELAY,
+};
+use crate::database_models::{GuildColumn, GuildRow};
+use crate::normalization::Normalizer;
+use crate::opt_ext::OptionTryUnwrap as _;
 use crate::structs::{
-    Context, Data, LastToXsaidTracker, LastXsaidInfo, RegexCache, Result, TTSMode, TTSServiceError,
+    Context, Data, LastToXsaidTracker, LinkReadingMode, NameSource, RegexCache, Result, TTSMode,
+    TTSServiceError,
 };
+use crate::translations::{Translations, DEFAULT_LOCALE};
+use crate::xsaid_template::{fill_phrase, TemplateValues, XsaidTemplate};
 
 pub(crate) fn timestamp_in_future(ts: serenity::Timestamp) -> bool {
     *ts > chrono::Utc::now()
@@ -25,17 +40,115 @@ pub fn push_permission_names(buffer: &mut String, permissions: serenity::Permiss
     }
 }
 
-pub async fn remove_premium(data: &Data, guild_id: serenity::GuildId) -> Result<()> {
-    tokio::try_join!(
-        data.guilds_db
-            .set_one(guild_id.into(), "premium_user", None::<i64>),
-        data.guilds_db
-            .set_one(guild_id.into(), "voice_mode", TTSMode::default()),
-    )?;
+/// Finds the parent channel of a thread, such as a forum post, if the channel is a cached thread.
+#[must_use]
+pub fn thread_parent(
+    guild: &serenity::Guild,
+    channel_id: serenity::ChannelId,
+) -> Option<serenity::ChannelId> {
+    guild
+        .threads
+        .iter()
+        .find(|thread| thread.id == channel_id)
+        .and_then(|thread| thread.parent_id)
+}
+
+/// Checks if a channel is the setup channel, or a thread of it which should be read.
+///
+/// Posts in a forum setup channel are always read, but threads of a text setup channel
+/// are only read if `read_threads` is enabled.
+#[must_use]
+pub fn is_setup_channel(
+    guild: &serenity::Guild,
+    guild_row: &GuildRow,
+    channel_id: serenity::ChannelId,
+) -> bool {
+    let Some(setup_channel_id) = guild_row.channel else {
+        return false;
+    };
+
+    if setup_channel_id == channel_id {
+        return true;
+    }
 
+    if thread_parent(guild, channel_id) != Some(setup_channel_id) {
+        return false;
+    }
+
+    guild_row.read_threads()
+        || guild
+            .channels
+            .get(&setup_channel_id)
+            .is_some_and(|channel| channel.kind == serenity::ChannelType::Forum)
+}
+
+/// Checks if permissions in a stage channel make a member a stage moderator, as Discord defines them.
+#[must_use]
+pub fn is_stage_moderator(permissions: serenity::Permissions) -> bool {
+    permissions.contains(
+        serenity::Permissions::MANAGE_CHANNELS
+            | serenity::Permissions::MUTE_MEMBERS
+            | serenity::Permissions::MOVE_MEMBERS,
+    )
+}
+
+/// After joining a stage channel, becomes a speaker if possible, otherwise requests to speak.
+///
+/// This is best-effort, as the bot has already joined and can still be made a speaker manually.
+pub async fn request_stage_speak(
+    http: &serenity::Http,
+    cache: &serenity::Cache,
+    guild_row: &GuildRow,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) {
+    if !guild_row.stage_auto_speak() {
+        return;
+    }
+
+    if let Err(err) = edit_stage_voice_state(http, cache, guild_id, channel_id).await {
+        tracing::warn!("Failed to request to speak in stage {channel_id}: {err:?}");
+    }
+}
+
+async fn edit_stage_voice_state(
+    http: &serenity::Http,
+    cache: &serenity::Cache,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> Result<()> {
+    let bot_id = cache.current_user().id;
+    let (channel, can_speak) = {
+        let guild = cache.guild(guild_id).try_unwrap()?;
+        let channel = guild.channels.get(&channel_id).try_unwrap()?;
+        if channel.kind != serenity::ChannelType::Stage {
+            return Ok(());
+        }
+
+        let bot_member = guild.members.get(&bot_id).try_unwrap()?;
+        let permissions = guild.user_permissions_in(channel, bot_member);
+        (channel.clone(), is_stage_moderator(permissions))
+    };
+
+    let builder = if can_speak {
+        serenity::EditVoiceState::new().suppress(false)
+    } else {
+        serenity::EditVoiceState::new().request_to_speak(true)
+    };
+
+    channel.edit_own_voice_state(http, builder).await?;
     Ok(())
 }
 
+pub async fn remove_premium(data: &Data, guild_id: serenity::GuildId) -> Result<()> {
+    data.guilds_db
+        .set_many(guild_id.into())
+        .set(GuildColumn::PremiumUser, None::<i64>)
+        .set(GuildColumn::VoiceMode, TTSMode::default())
+        .execute()
+        .await
+}
+
 pub async fn dm_generic(
     ctx: &serenity::Context,
     author: &serenity::User,
@@ -65,32 +178,315 @@ pub async fn dm_generic(
     Ok((target_tag, sent.embeds.into_iter().next().unwrap()))
 }
 
-pub async fn fetch_audio(
-    reqwest: &reqwest::Client,
+static NEXT_TTS_SERVICE: AtomicUsize = AtomicUsize::new(0);
+
+enum FetchError {
+    /// The tts-service may succeed if asked again, such as timeouts or server errors.
+    Retryable(anyhow::Error),
+    /// The request itself was bad, so asking again would give the same error.
+    Permanent(anyhow::Error),
+}
+
+/// Moves a request built against `tts_service` onto another instance.
+fn with_service(url: &reqwest::Url, service: &reqwest::Url) -> reqwest::Url {
+    let mut new_url = service.clone();
+    new_url.set_path(url.path());
+    new_url.set_query(url.query());
+    new_url
+}
+
+async fn fetch_audio_once(
+    data: &Data,
     url: reqwest::Url,
-    auth_key: Option<&str>,
-) -> Result<Option<reqwest::Response>> {
-    let resp = reqwest
+) -> Result<Option<reqwest::Response>, FetchError> {
+    let auth_key = data.config.tts_service_auth_key.as_deref();
+    let resp = data
+        .tts_reqwest
         .get(url)
         .header(reqwest::header::AUTHORIZATION, auth_key.unwrap_or(""))
         .send()
-        .await?;
+        .await
+        .map_err(|err| FetchError::Retryable(err.into()))?;
+
+    let status = resp.status();
+    let retryable_status =
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
 
     match resp.error_for_status_ref() {
         Ok(_) => Ok(Some(resp)),
         Err(backup_err) => match resp.json::<TTSServiceError>().await {
+            Ok(err) if err.code.should_ignore() => Ok(None),
             Ok(err) => {
-                if err.code.should_ignore() {
-                    Ok(None)
+                let retryable = retryable_status && err.code.is_retryable();
+                let err = anyhow::anyhow!("Error fetching audio: {}", err.display);
+                Err(if retryable {
+                    FetchError::Retryable(err)
                 } else {
-                    Err(anyhow::anyhow!("Error fetching audio: {}", err.display))
-                }
+                    FetchError::Permanent(err)
+                })
             }
-            Err(_) => Err(backup_err.into()),
+            Err(_) if retryable_status => Err(FetchError::Retryable(backup_err.into())),
+            Err(_) => Err(FetchError::Permanent(backup_err.into())),
         },
     }
 }
 
+/// Fetches from `services[index]`, asking the next instance as well if the first is too slow.
+async fn fetch_audio_hedged(
+    data: &Data,
+    url: &reqwest::Url,
+    services: &[&reqwest::Url],
+    index: usize,
+) -> Result<Option<reqwest::Response>, FetchError> {
+    let primary = fetch_audio_once(data, with_service(url, services[index]));
+    let Some(hedge_after) = data
+        .config
+        .tts_service_hedge_after_ms
+        .filter(|_| services.len() > 1)
+    else {
+        return primary.await;
+    };
+
+    tokio::pin!(primary);
+    tokio::select! {
+        res = &mut primary => return res,
+        () = tokio::time::sleep(Duration::from_millis(hedge_after)) => {}
+    }
+
+    let backup_service = services[(index + 1) % services.len()];
+    let backup = fetch_audio_once(data, with_service(url, backup_service));
+    tokio::pin!(backup);
+
+    // Take whichever succeeds first, only failing if both do.
+    tokio::select! {
+        res = &mut primary => match res {
+            Ok(resp) => Ok(resp),
+            Err(_) => backup.await,
+        },
+        res = &mut backup => match res {
+            Ok(resp) => Ok(resp),
+            Err(_) => primary.await,
+        },
+    }
+}
+
+/// Fetches audio from the tts-service, retrying with backoff and failing over between instances.
+///
+/// The final outcome is recorded in the mode's circuit breaker.
+pub async fn fetch_audio(
+    data: &Data,
+    url: reqwest::Url,
+    mode: TTSMode,
+) -> Result<Option<reqwest::Response>> {
+    let services: Vec<_> = std::iter::once(&data.config.tts_service)
+        .chain(&data.config.tts_service_failover)
+        .collect();
+
+    let max_retries = data
+        .config
+        .tts_service_max_retries
+        .unwrap_or(DEFAULT_TTS_MAX_RETRIES);
+
+    let start = NEXT_TTS_SERVICE.fetch_add(1, Ordering::Relaxed);
+    let mut attempt = 0;
+    loop {
+        let index = (start + usize::from(attempt)) % services.len();
+        let err = match fetch_audio_hedged(data, &url, &services, index).await {
+            Ok(resp) => {
+                data.circuit_breakers.record(mode, true);
+                return Ok(resp);
+            }
+            // Only outages count as failures, not errors caused by the request itself.
+            Err(FetchError::Permanent(err)) => {
+                data.circuit_breakers.record(mode, true);
+                return Err(err);
+            }
+            Err(FetchError::Retryable(err)) => err,
+        };
+
+        if attempt >= max_retries {
+            data.circuit_breakers.record(mode, false);
+            return Err(err);
+        }
+
+        let backoff = TTS_RETRY_BASE_DELAY * 2_u32.pow(u32::from(attempt));
+        let jitter = rand::rng().random_range(0..=backoff.as_millis() as u64 / 2);
+        tracing::warn!("Retrying tts-service request after error: {err:?}");
+        tokio::time::sleep(backoff + Duration::from_millis(jitter)).await;
+
+        attempt += 1;
+    }
+}
+
+/// Wraps a tts-service response for songbird, streaming the body so playback starts on the first frames.
+pub fn audio_to_input(audio: reqwest::Response) -> Result<songbird::input::Input> {
+    let hint = audio
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .map(|ct| {
+            let mut hint = songbird::input::core::probe::Hint::new();
+            hint.mime_type(ct.to_str()?);
+            Ok::<_, anyhow::Error>(hint)
+        })
+        .transpose()?;
+
+    let stream = Box::new(ResponseStream::new(audio));
+    let input = Box::new(songbird::input::AsyncAdapterStream::new(
+        stream,
+        AUDIO_STREAM_BUFFER,
+    ));
+
+    let wrapped_audio =
+        songbird::input::LiveInput::Raw(songbird::input::AudioStream { input, hint });
+
+    Ok(songbird::input::Input::Live(wrapped_audio, None))
+}
+
+/// Fetches audio from the tts-service for songbird, synthesizing it locally instead if
+/// the tts-service fails and the `local-tts` feature is configured.
+///
+/// With local TTS configured, the tts-service is skipped entirely while the mode's circuit
+/// is open or no voices have been loaded for it, instead of waiting out the retries.
+#[cfg_attr(not(feature = "local-tts"), expect(unused_variables))]
+pub async fn fetch_input(
+    data: &Data,
+    url: reqwest::Url,
+    mode: TTSMode,
+    content: &str,
+    voice: &str,
+) -> Result<Option<songbird::input::Input>> {
+    #[cfg(feature = "local-tts")]
+    if let Some(local_tts) = &data.local_tts
+        && (data.circuit_breakers.is_open(mode) || !data.voices.load().has_voices(mode))
+    {
+        return synthesize_locally(local_tts, mode, content, voice).await;
+    }
+
+    let err = match fetch_audio(data, url, mode).await {
+        Ok(Some(audio)) => return audio_to_input(audio).map(Some),
+        Ok(None) => return Ok(None),
+        Err(err) => err,
+    };
+
+    #[cfg(feature = "local-tts")]
+    if let Some(local_tts) = &data.local_tts {
+        tracing::warn!("Falling back to local TTS after tts-service error: {err:?}");
+        return synthesize_locally(local_tts, mode, content, voice).await;
+    }
+
+    Err(err)
+}
+
+#[cfg(feature = "local-tts")]
+async fn synthesize_locally(
+    local_tts: &crate::local_tts::LocalTTSConfig,
+    mode: TTSMode,
+    content: &str,
+    voice: &str,
+) -> Result<Option<songbird::input::Input>> {
+    // Only eSpeak voices are understood by the local synthesizer.
+    let voice = (mode == TTSMode::eSpeak).then_some(voice);
+    crate::local_tts::synthesize(local_tts, content, voice)
+        .await
+        .map(Some)
+}
+
+/// Generates TTS in the guild's default voice, for speech not coming from a user's message.
+pub async fn fetch_guild_tts(
+    data: &Data,
+    http: &serenity::Http,
+    guild_id: serenity::GuildId,
+    content: &str,
+) -> Result<Option<songbird::input::Input>> {
+    let premium = data.guild_premium(http, guild_id).await?;
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    let (mut voice, mut mode) = data.parse_guild_with_premium(guild_id, premium).await?;
+    if let Some(fallback) = data.fallback_mode(mode, premium) {
+        voice = Cow::Borrowed(fallback.default_voice());
+        mode = fallback;
+    }
+
+    let speaking_rate = mode
+        .speaking_rate_info()
+        .map(|info| info.default)
+        .unwrap_or("1.0");
+    let url = prepare_url(
+        data.config.tts_service.clone(),
+        content,
+        &voice,
+        mode,
+        speaking_rate,
+        &guild_row.msg_length.to_arraystring(),
+        data.target_lang(&guild_row, premium),
+    );
+
+    fetch_input(data, url, mode, content, &voice).await
+}
+
+/// Parses a standard five field cron expression, evaluated in UTC.
+pub fn parse_cron(expression: &str) -> Result<cron::Schedule> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+        anyhow::bail!("Cron expressions must have exactly five fields");
+    };
+
+    let day_of_week = cron_day_names(day_of_week)?;
+
+    // The cron crate requires a leading seconds field, which users should not have to think about.
+    Ok(format!("0 {minute} {hour} {day_of_month} {month} {day_of_week}").parse()?)
+}
+
+/// Replaces standard day of week numbers (0-7, Sunday being 0 or 7) with names, as the cron crate
+/// numbers days from 1 with Sunday as 1.
+fn cron_day_names(field: &str) -> Result<String> {
+    const DAY_NAMES: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+    let mut out = String::with_capacity(field.len());
+    for (i, item) in field.split(',').enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+
+        for (j, day) in range.split('-').enumerate() {
+            if j != 0 {
+                out.push('-');
+            }
+
+            if day.bytes().all(|b| b.is_ascii_digit()) && !day.is_empty() {
+                let Some(name) = day.parse::<usize>().ok().and_then(|d| DAY_NAMES.get(d)) else {
+                    anyhow::bail!("Day of week {day} is out of range");
+                };
+
+                out.push_str(name);
+            } else {
+                out.push_str(day);
+            }
+        }
+
+        if let Some(step) = step {
+            out.push('/');
+            out.push_str(step);
+        } else if range.ends_with("-7") {
+            // `FRI-SUN` would wrap around in the cron crate, so Sunday is appended separately.
+            out.truncate(out.len() - "SUN".len());
+            out.push_str("SAT,SUN");
+        }
+    }
+
+    Ok(out)
+}
+
+#[must_use]
+pub fn next_cron_run(schedule: &cron::Schedule, after: i64) -> Option<i64> {
+    let after = chrono::DateTime::from_timestamp(after, 0)?;
+    schedule.after(&after).next().map(|next| next.timestamp())
+}
+
 #[must_use]
 pub fn prepare_url(
     mut tts_service: reqwest::Url,
@@ -122,15 +518,25 @@ pub fn prepare_url(
 }
 
 #[must_use]
-pub fn random_footer(server_invite: &str, client_id: serenity::UserId) -> Cow<'static, str> {
-    match rand::rng().random_range(0..5) {
-        0 => Cow::Owned(format!("If you find a bug or want to ask a question, join the support server: {server_invite}")),
-        1 => Cow::Owned(format!("You can vote for me or review me on wumpus.store!\nhttps://wumpus.store/bot/{client_id}?ref=tts")),
-        2 => Cow::Owned(format!("You can vote for me or review me on top.gg!\nhttps://top.gg/bot/{client_id}")),
-        3 => Cow::Borrowed("If you want to support the development and hosting of TTS Bot, check out `/premium`!"),
-        4 => Cow::Borrowed("There are loads of customizable settings, check out `/help set`"),
-        _ => unreachable!()
-    }
+pub fn random_footer(
+    translations: &Translations,
+    locale: &str,
+    server_invite: &str,
+    client_id: serenity::UserId,
+) -> String {
+    let key = match rand::rng().random_range(0..5) {
+        0 => "footer.support",
+        1 => "footer.vote_wumpus",
+        2 => "footer.vote_topgg",
+        3 => "footer.premium",
+        4 => "footer.settings",
+        _ => unreachable!(),
+    };
+
+    translations
+        .get(locale, key)
+        .replace("{server_invite}", server_invite)
+        .replace("{client_id}", &client_id.to_string())
 }
 
 fn strip_emoji<'c>(regex_cache: &RegexCache, content: &'c str) -> Cow<'c, str> {
@@ -154,33 +560,18 @@ fn make_emoji_readable<'c>(regex_cache: &RegexCache, content: &'c str) -> Cow<'c
         })
 }
 
-fn parse_acronyms(original: &str) -> String {
-    original
-        .split(' ')
-        .map(|word| match word {
-            "iirc" => "if I recall correctly",
-            "afaik" => "as far as I know",
-            "wdym" => "what do you mean",
-            "imo" => "in my opinion",
-            "brb" => "be right back",
-            "wym" => "what you mean",
-            "irl" => "in real life",
-            "jk" => "just kidding",
-            "btw" => "by the way",
-            ":)" => "smiley face",
-            "gtg" => "got to go",
-            "rn" => "right now",
-            ":(" => "sad face",
-            "ig" => "i guess",
-            "ppl" => "people",
-            "rly" => "really",
-            "cya" => "see ya",
-            "ik" => "i know",
-            "@" => "at",
-            "™️" => "tm",
-            _ => word,
-        })
-        .join(" ")
+fn extension_to_format(extension: &str) -> &'static str {
+    match extension {
+        "bmp" | "gif" | "ico" | "png" | "psd" | "svg" | "jpg" => "an image file",
+        "mid" | "midi" | "mp3" | "ogg" | "wav" | "wma" => "an audio file",
+        "avi" | "mp4" | "wmv" | "m4v" | "mpg" | "mpeg" => "a video file",
+        "zip" | "7z" | "rar" | "gz" | "xz" => "a compressed file",
+        "doc" | "docx" | "txt" | "odt" | "rtf" => "a text file",
+        "bat" | "sh" | "jar" | "py" | "php" => "a script file",
+        "apk" | "exe" | "msi" | "deb" => "a program file",
+        "dmg" | "iso" | "img" | "ima" => "a disk image",
+        _ => "a file",
+    }
 }
 
 fn attachments_to_format(attachments: &[serenity::Attachment]) -> Option<&'static str> {
@@ -189,16 +580,207 @@ fn attachments_to_format(attachments: &[serenity::Attachment]) -> Option<&'stati
     }
 
     let extension = attachments.first()?.filename.split('.').next_back()?;
-    match extension {
-        "bmp" | "gif" | "ico" | "png" | "psd" | "svg" | "jpg" => Some("an image file"),
-        "mid" | "midi" | "mp3" | "ogg" | "wav" | "wma" => Some("an audio file"),
-        "avi" | "mp4" | "wmv" | "m4v" | "mpg" | "mpeg" => Some("a video file"),
-        "zip" | "7z" | "rar" | "gz" | "xz" => Some("a compressed file"),
-        "doc" | "docx" | "txt" | "odt" | "rtf" => Some("a text file"),
-        "bat" | "sh" | "jar" | "py" | "php" => Some("a script file"),
-        "apk" | "exe" | "msi" | "deb" => Some("a program file"),
-        "dmg" | "iso" | "img" | "ima" => Some("a disk image"),
-        _ => Some("a file"),
+    Some(extension_to_format(extension))
+}
+
+/// Describes an attachment by its MIME type, falling back to its extension, along with its filename.
+fn describe_attachment(attachment: &serenity::Attachment) -> String {
+    let (stem, extension) = attachment
+        .filename
+        .rsplit_once('.')
+        .unwrap_or((&*attachment.filename, ""));
+
+    let mime_type = attachment.content_type.as_deref().unwrap_or_default();
+    let format = match mime_type.split_once('/') {
+        Some(("image", _)) => "an image",
+        Some(("audio", _)) => "an audio file",
+        Some(("video", _)) => "a video",
+        Some(("text", _)) => "a text file",
+        _ => extension_to_format(&extension.to_lowercase()),
+    };
+
+    let name = stem.replace(['_', '-'], " ");
+    let name = name.trim();
+    if name.is_empty()
+        || name.chars().count() > MAX_FILENAME_LENGTH
+        || GENERIC_FILENAMES.contains(&name.to_lowercase().as_str())
+    {
+        format.to_owned()
+    } else {
+        format!("{format} called {name}")
+    }
+}
+
+fn join_with_and(items: &[String]) -> Option<String> {
+    match items {
+        [] => None,
+        [item] => Some(item.clone()),
+        [rest @ .., last] => Some(format!("{} and {last}", rest.join(", "))),
+    }
+}
+
+/// Describes everything sent with a message other than its text, such as files, stickers, and polls.
+fn describe_extras(message: &serenity::Message) -> Option<String> {
+    let mut extras = Vec::new();
+
+    let is_forward = message
+        .message_reference
+        .as_ref()
+        .is_some_and(|reference| reference.kind == serenity::MessageReferenceKind::Forward);
+    if is_forward {
+        extras.push(String::from("a forwarded message"));
+    }
+
+    let is_voice_message = message
+        .flags
+        .is_some_and(|flags| flags.contains(serenity::MessageFlags::IS_VOICE_MESSAGE));
+    match message.attachments.as_slice() {
+        [] => {}
+        [_] if is_voice_message => extras.push(String::from("a voice message")),
+        [attachment] => extras.push(describe_attachment(attachment)),
+        attachments => extras.push(format!("{} files", attachments.len())),
+    }
+
+    for sticker in &message.sticker_items {
+        extras.push(format!("a sticker called {}", sticker.name));
+    }
+
+    if let Some(poll) = &message.poll {
+        extras.push(match &poll.question.text {
+            Some(question) => format!("a poll: {question}"),
+            None => String::from("a poll"),
+        });
+    }
+
+    // Embeds on user messages are just link previews, which are already read as links.
+    if message.author.bot() || message.webhook_id.is_some() {
+        match message.embeds.as_slice() {
+            [] => {}
+            [embed] => extras.push(match &embed.title {
+                Some(title) => format!("an embed titled {title}"),
+                None => String::from("an embed"),
+            }),
+            embeds => extras.push(format!("{} embeds", embeds.len())),
+        }
+    }
+
+    join_with_and(&extras)
+}
+
+/// Strips emoji and symbols from a name, returning `None` if nothing readable is left.
+#[must_use]
+pub fn readable_name<'a>(regex_cache: &RegexCache, name: &'a str) -> Option<Cow<'a, str>> {
+    let name = regex_cache.unreadable_name_chars.replace_all(name, " ");
+    if !name.chars().any(char::is_alphanumeric) {
+        return None;
+    }
+
+    if name.trim() == name && !name.contains("  ") {
+        Some(name)
+    } else {
+        Some(Cow::Owned(name.split_whitespace().join(" ")))
+    }
+}
+
+/// Picks the name to read out for a member, skipping any that cannot be read aloud.
+///
+/// The spoken name set with `/set nick` always comes first, followed by the names allowed
+/// by the server's `name_source`, falling back to the username if none are readable.
+#[must_use]
+pub fn spoken_name<'a>(
+    regex_cache: &RegexCache,
+    name_source: NameSource,
+    nickname: Option<&'a str>,
+    member_nick: Option<&'a str>,
+    user: &'a serenity::User,
+) -> Cow<'a, str> {
+    let global_name = user.global_name.as_deref();
+    let candidates = match name_source {
+        NameSource::Nickname => [nickname, member_nick, global_name],
+        NameSource::DisplayName => [nickname, global_name, None],
+        NameSource::Username => [nickname, None, None],
+    };
+
+    candidates
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(user.name.as_str()))
+        .find_map(|name| readable_name(regex_cache, name))
+        .unwrap_or(Cow::Borrowed(&user.name))
+}
+
+/// Finds the name of the author of the message being replied to, preferring their server nickname.
+fn reply_name(
+    cache: &serenity::Cache,
+    guild_id: serenity::GuildId,
+    message: &serenity::Message,
+) -> Option<String> {
+    if message.kind != serenity::MessageType::InlineReply {
+        return None;
+    }
+
+    let author = &message.referenced_message.as_ref()?.author;
+    let nick = cache.guild(guild_id).and_then(|guild| {
+        let member = guild.members.get(&author.id)?;
+        member.nick.as_deref().map(String::from)
+    });
+
+    Some(nick.unwrap_or_else(|| {
+        author
+            .global_name
+            .as_deref()
+            .unwrap_or(&author.name)
+            .to_owned()
+    }))
+}
+
+fn link_domain(link: &str) -> Option<String> {
+    let url = reqwest::Url::parse(link).ok()?;
+    let host = url.host_str()?;
+
+    Some(host.strip_prefix("www.").unwrap_or(host).to_owned())
+}
+
+/// Finds the spoken name of a website, checking parent domains so `m.youtube.com` is still `YouTube`.
+fn site_name<'a>(domain: &str, link_names: &'a HashMap<String, String>) -> Option<&'a str> {
+    let mut candidate = domain;
+    loop {
+        if let Some(name) = link_names.get(candidate) {
+            return Some(name);
+        }
+
+        if let Some((_, name)) = KNOWN_SITES.iter().find(|(site, _)| *site == candidate) {
+            return Some(name);
+        }
+
+        candidate = candidate.split_once('.')?.1;
+    }
+}
+
+fn describe_link(
+    link: Option<&str>,
+    link_reading: LinkReadingMode,
+    link_names: &HashMap<String, String>,
+) -> String {
+    let domain = link.and_then(link_domain);
+    match (link_reading, domain) {
+        (LinkReadingMode::Domain, Some(domain)) => format!("a link to {domain}"),
+        (LinkReadingMode::KnownSites, Some(domain)) => match site_name(&domain, link_names) {
+            Some(name) if name.to_lowercase().starts_with(['a', 'e', 'i', 'o', 'u']) => {
+                format!("an {name} link")
+            }
+            Some(name) => format!("a {name} link"),
+            None => String::from("a link"),
+        },
+        _ => String::from("a link"),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
@@ -211,28 +793,83 @@ fn remove_repeated_chars(content: &str, limit: u8) -> String {
     out
 }
 
+/// Splits content into chunks of whole sentences, each up to `max_length` bytes where possible.
+///
+/// Sentences longer than `max_length` are split between words instead.
+#[must_use]
+pub fn split_sentences(content: &str, max_length: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0;
+    let mut last_boundary = None;
+
+    let mut chars = content.char_indices().peekable();
+    while let Some((index, char)) = chars.next() {
+        let next_is_space = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        let is_boundary = match char {
+            '.' | '!' | '?' | '…' => next_is_space,
+            '\n' => true,
+            _ => false,
+        };
+
+        let end = index + char.len_utf8();
+        if end - chunk_start > max_length {
+            // Prefer the last sentence boundary, then the last space, then a hard split.
+            let split_at = last_boundary
+                .or_else(|| {
+                    content[chunk_start..index]
+                        .rfind(' ')
+                        .map(|i| chunk_start + i + 1)
+                })
+                .filter(|split_at| *split_at > chunk_start)
+                .unwrap_or(index);
+
+            chunks.push(&content[chunk_start..split_at]);
+            chunk_start = split_at;
+            last_boundary = None;
+        }
+
+        if is_boundary {
+            last_boundary = Some(end);
+        }
+    }
+
+    chunks.push(&content[chunk_start..]);
+    chunks
+        .into_iter()
+        .map(str::trim)
+        .filter(|chunk| !chunk.is_empty())
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn clean_msg(
     content: &str,
 
-    user: &serenity::User,
+    message: &serenity::Message,
     cache: &serenity::Cache,
     guild_id: serenity::GuildId,
     member_nick: Option<&str>,
-    attachments: &[serenity::Attachment],
 
-    voice: &str,
+    normalizer: Option<&Normalizer>,
+    link_reading: LinkReadingMode,
+    link_names: &HashMap<String, String>,
     xsaid: bool,
+    xsaid_window: u16,
+    xsaid_reannounce: bool,
     skip_emoji: bool,
+    describe_messages: bool,
     repeated_limit: Option<NonZeroU8>,
     nickname: Option<&str>,
+    name_source: NameSource,
     use_new_formatting: bool,
+    xsaid_template: Option<&XsaidTemplate>,
+    phrases: Phrases<'_>,
 
     regex_cache: &RegexCache,
     last_to_xsaid_tracker: &LastToXsaidTracker,
 ) -> String {
-    let (contained_url, mut content) = if content == "?" {
-        (false, String::from("what"))
+    let (link, mut content) = if content == "?" {
+        (None, String::from("what"))
     } else {
         let mut content = if skip_emoji {
             strip_emoji(regex_cache, content)
@@ -246,41 +883,123 @@ pub fn clean_msg(
             }
         }
 
-        if voice.starts_with("en") {
-            content = Cow::Owned(parse_acronyms(&content));
-        }
-
+        // Emails are kept as `None`, as they have no domain worth reading out
+        let mut links = Vec::new();
         let filtered_content: String = linkify::LinkFinder::new()
             .spans(&content)
-            .filter(|span| span.kind().is_none())
-            .map(|span| span.as_str())
+            .filter_map(|span| match span.kind() {
+                Some(linkify::LinkKind::Url) => {
+                    links.push(Some(span.as_str()));
+                    None
+                }
+                Some(_) => {
+                    links.push(None);
+                    None
+                }
+                None => Some(span.as_str()),
+            })
             .collect();
 
-        (content != filtered_content, filtered_content)
+        let link = match links.as_slice() {
+            [] => None,
+            [link] => Some(describe_link(*link, link_reading, link_names)),
+            // Dropping links has always read them as just "a link", however many there are.
+            [link, ..] if link_reading == LinkReadingMode::Drop => {
+                Some(describe_link(*link, link_reading, link_names))
+            }
+            _ => Some(String::from("multiple links")),
+        };
+
+        let filtered_content = match normalizer {
+            Some(normalizer) => normalizer.normalize(&filtered_content),
+            None => filtered_content,
+        };
+
+        (link, filtered_content)
     };
 
+    let user = &message.author;
+    let voice_channel_id = cache
+        .guild(guild_id)
+        .and_then(|guild| guild.voice_states.get(&user.id)?.channel_id);
+
     let announce_name = xsaid
-        && last_to_xsaid_tracker.get(&guild_id).is_none_or(|state| {
+        && voice_channel_id.is_none_or(|channel_id| {
             let guild = cache.guild(guild_id).unwrap();
-            state.should_announce_name(&guild, user.id)
+            let window = Duration::from_secs(xsaid_window.into());
+            last_to_xsaid_tracker.should_announce_name(
+                &guild,
+                channel_id,
+                user.id,
+                window,
+                xsaid_reannounce,
+            )
         });
 
-    let attached_file_format = attachments_to_format(attachments);
-    let said_name = announce_name.then(|| {
-        nickname
-            .or(member_nick)
-            .or(user.global_name.as_deref())
-            .unwrap_or(&user.name)
-    });
+    let (attached_file_format, reply_to) = if describe_messages {
+        let reply_to = reply_name(cache, guild_id, message);
+        (describe_extras(message).map(Cow::Owned), reply_to)
+    } else {
+        let attached_file_format = attachments_to_format(&message.attachments);
+        (attached_file_format.map(Cow::Borrowed), None)
+    };
+
+    let name =
+        announce_name.then(|| spoken_name(regex_cache, name_source, nickname, member_nick, user));
+    let name = name.as_deref();
 
-    if use_new_formatting {
-        format_message(&mut content, said_name, contained_url, attached_file_format);
+    let link = link.as_deref();
+    let attached_file_format = attached_file_format.as_deref();
+    // Templates such as `{name} said: {message}` would be read without the name when it is
+    // not being announced, so the default phrasing is used instead.
+    let xsaid_template =
+        xsaid_template.filter(|template| name.is_some() || !template.requires_name());
+
+    if let Some(xsaid_template) = xsaid_template {
+        content = xsaid_template.render(&TemplateValues {
+            name,
+            link,
+            message: Some(&content),
+            attachment: attached_file_format,
+            reply: reply_to.as_deref(),
+        });
     } else {
-        format_message_legacy(&mut content, said_name, contained_url, attached_file_format);
+        let said_name = name.map(|name| match &reply_to {
+            Some(reply_to) => Cow::Owned(phrases.fill(
+                "xsaid.replying_to",
+                &TemplateValues {
+                    name: Some(name),
+                    reply: Some(reply_to),
+                    ..TemplateValues::default()
+                },
+            )),
+            None => Cow::Borrowed(name),
+        });
+
+        let said_name = said_name.as_deref();
+        if use_new_formatting {
+            format_message(&mut content, said_name, link, attached_file_format, phrases);
+        } else {
+            format_message_legacy(&mut content, said_name, link, attached_file_format);
+        }
+
+        if said_name.is_none()
+            && let Some(reply_to) = reply_to
+            && !content.is_empty()
+        {
+            content = phrases.fill(
+                "xsaid.replying_to_message",
+                &TemplateValues {
+                    message: Some(&content),
+                    reply: Some(&reply_to),
+                    ..TemplateValues::default()
+                },
+            );
+        }
     }
 
-    if xsaid {
-        last_to_xsaid_tracker.insert(guild_id, LastXsaidInfo::new(user.id));
+    if xsaid && let Some(channel_id) = voice_channel_id {
+        last_to_xsaid_tracker.record(guild_id, channel_id, user.id);
     }
 
     if let Some(repeated_limit) = repeated_limit {
@@ -293,20 +1012,18 @@ pub fn clean_msg(
 pub fn format_message_legacy(
     content: &mut String,
     said_name: Option<&str>,
-    contained_url: bool,
+    link: Option<&str>,
     attached_file_format: Option<&str>,
 ) {
     use std::fmt::Write;
 
     if let Some(said_name) = said_name {
-        if contained_url {
-            let suffix = if content.is_empty() {
-                "a link."
+        if let Some(link) = link {
+            if content.is_empty() {
+                write!(content, " {link}.").unwrap();
             } else {
-                "and sent a link"
-            };
-
-            write!(content, " {suffix}",).unwrap();
+                write!(content, " and sent {link}").unwrap();
+            }
         }
 
         *content = match attached_file_format {
@@ -314,76 +1031,92 @@ pub fn format_message_legacy(
             Some(file_format) => format!("{said_name} sent {file_format} and said {content}"),
             None => format!("{said_name} said: {content}"),
         }
-    } else if contained_url {
-        let suffix = if content.is_empty() {
-            " a link."
+    } else if let Some(link) = link {
+        if content.is_empty() {
+            write!(content, " {link}.").unwrap();
         } else {
-            ". This message contained a link"
-        };
+            write!(content, ". This message contained {link}").unwrap();
+        }
+    }
+}
+
+/// Looks up the fixed phrases used by [`format_message`], in the language messages are translated to.
+#[derive(Clone, Copy)]
+pub struct Phrases<'a> {
+    translations: &'a Translations,
+    locale: &'a str,
+}
 
-        write!(content, "{suffix}",).unwrap();
+impl<'a> Phrases<'a> {
+    #[must_use]
+    pub fn new(translations: &'a Translations, target_lang: Option<&str>) -> Self {
+        let locale = target_lang
+            .and_then(|lang| translations.resolve(lang))
+            .unwrap_or(DEFAULT_LOCALE);
+
+        Self {
+            translations,
+            locale,
+        }
+    }
+
+    fn fill(self, key: &'static str, values: &TemplateValues<'_>) -> String {
+        fill_phrase(self.translations.get(self.locale, key), values)
     }
 }
 
 pub fn format_message(
     content: &mut String,
     said_name: Option<&str>,
-    contained_url: bool,
+    link: Option<&str>,
     attached_file_format: Option<&str>,
+    phrases: Phrases<'_>,
 ) {
-    match (
-        said_name,
-        content.trim(),
-        contained_url,
-        attached_file_format,
-    ) {
-        (Some(said_name), "", true, Some(format)) => {
-            *content = format!("{said_name} sent a link and attached {format}");
-        }
-        (Some(said_name), "", true, None) => {
-            *content = format!("{said_name} sent a link");
-        }
-        (Some(said_name), "", false, Some(format)) => {
-            *content = format!("{said_name} sent {format}");
-        }
+    let msg = content.trim();
+    let key = match (said_name, msg, link, attached_file_format) {
+        (Some(_), "", Some(_), Some(_)) => "xsaid.sent_link_attachment",
+        (Some(_), "", Some(_), None) => "xsaid.sent_link",
+        (Some(_), "", None, Some(_)) => "xsaid.sent_attachment",
         // Fallback, this shouldn't occur
-        (Some(said_name), "", false, None) => {
-            *content = format!("{said_name} sent a message");
-        }
-        (Some(said_name), msg, true, Some(format)) => {
-            *content = format!("{said_name} sent a link, attached {format}, and said {msg}");
-        }
-        (Some(said_name), msg, true, None) => {
-            *content = format!("{said_name} sent a link and said {msg}");
-        }
-        (Some(said_name), msg, false, Some(format)) => {
-            *content = format!("{said_name} sent {format} and said {msg}");
-        }
-        (Some(said_name), msg, false, None) => {
-            *content = format!("{said_name} said: {msg}");
-        }
-        (None, "", true, Some(format)) => {
-            *content = format!("A link and {format}");
+        (Some(_), "", None, None) => "xsaid.sent_message",
+        (Some(_), _, Some(_), Some(_)) => "xsaid.sent_link_attachment_said",
+        (Some(_), _, Some(_), None) => "xsaid.sent_link_said",
+        (Some(_), _, None, Some(_)) => "xsaid.sent_attachment_said",
+        (Some(_), _, None, None) => "xsaid.said",
+        (None, "", Some(link), Some(format)) => {
+            let values = TemplateValues {
+                link: Some(link),
+                attachment: Some(format),
+                ..TemplateValues::default()
+            };
+
+            *content = capitalize(&phrases.fill("xsaid.link_attachment", &values));
+            return;
         }
-        (None, "", true, None) => {
-            "A link".clone_into(content);
+        (None, "", Some(link), None) => {
+            *content = capitalize(link);
+            return;
         }
-        (None, "", false, Some(format)) => {
+        (None, "", None, Some(format)) => {
             format.clone_into(content);
+            return;
         }
         // Again, fallback, there is nothing to say
-        (None, "", false, None) => {}
-        (None, msg, true, Some(format)) => {
-            *content = format!("{msg} with {format} and a link");
-        }
-        (None, msg, true, None) => {
-            *content = format!("{msg} with a link");
-        }
-        (None, msg, false, Some(format)) => {
-            *content = format!("{msg} with {format}");
-        }
-        (None, _msg, false, None) => {}
-    }
+        (None, _, None, None) => return,
+        (None, _, Some(_), Some(_)) => "xsaid.message_link_attachment",
+        (None, _, Some(_), None) => "xsaid.message_link",
+        (None, _, None, Some(_)) => "xsaid.message_attachment",
+    };
+
+    let values = TemplateValues {
+        name: said_name,
+        message: Some(msg),
+        link,
+        attachment: attached_file_format,
+        reply: None,
+    };
+
+    *content = phrases.fill(key, &values);
 }
 
 pub fn confirm_dialog_buttons<'a>(positive: &'a str, negative: &'a str) -> [CreateButton<'a>; 2] {
@@ -438,3 +1171,70 @@ pub async fn confirm_dialog(
 
     confirm_dialog_wait(ctx.serenity_context(), message.id, ctx.author().id).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{cron_day_names, next_cron_run, parse_cron, split_sentences};
+
+    /// 2024-01-01 00:00 UTC, a Monday.
+    const MONDAY: i64 = 1_704_067_200;
+    const HOUR: i64 = 60 * 60;
+    const DAY: i64 = HOUR * 24;
+
+    fn next_run(expression: &str) -> i64 {
+        let schedule = parse_cron(expression).expect("cron expression should parse");
+        next_cron_run(&schedule, MONDAY).expect("schedule should have a next run")
+    }
+
+    #[test]
+    fn cron_days() {
+        assert_eq!(cron_day_names("0").unwrap(), "SUN");
+        assert_eq!(cron_day_names("7").unwrap(), "SUN");
+        assert_eq!(cron_day_names("1-5").unwrap(), "MON-FRI");
+        assert_eq!(cron_day_names("5-7").unwrap(), "FRI-SAT,SUN");
+        assert_eq!(cron_day_names("*/2").unwrap(), "*/2");
+        assert_eq!(cron_day_names("1-5/2").unwrap(), "MON-FRI/2");
+        assert_eq!(cron_day_names("MON-FRI").unwrap(), "MON-FRI");
+        assert_eq!(cron_day_names("0,3").unwrap(), "SUN,WED");
+        assert!(cron_day_names("8").is_err());
+    }
+
+    #[test]
+    fn cron_schedules() {
+        assert_eq!(next_run("0 12 * * 0"), MONDAY + 6 * DAY + 12 * HOUR);
+        assert_eq!(next_run("0 12 * * 7"), MONDAY + 6 * DAY + 12 * HOUR);
+        assert_eq!(next_run("0 12 * * 1-5"), MONDAY + 12 * HOUR);
+        assert_eq!(next_run("0 12 * * MON-FRI"), MONDAY + 12 * HOUR);
+        assert_eq!(next_run("0 12 * * */2"), MONDAY + DAY + 12 * HOUR);
+        assert_eq!(
+            next_run("30 20 * * FRI"),
+            MONDAY + 4 * DAY + 20 * HOUR + 30 * 60
+        );
+
+        assert!(parse_cron("0 12 * *").is_err());
+        assert!(parse_cron("0 0 12 * * 1").is_err());
+    }
+
+    #[test]
+    fn sentences() {
+        assert_eq!(
+            split_sentences("Hello there. How are you?", 100),
+            ["Hello there. How are you?"]
+        );
+        assert_eq!(
+            split_sentences("Hello there. How are you? Good!", 15),
+            ["Hello there.", "How are you?", "Good!"]
+        );
+        assert_eq!(
+            split_sentences("Pi is 3.14 or so.", 12),
+            ["Pi is 3.14", "or so."]
+        );
+        assert_eq!(
+            split_sentences("one two three four", 9),
+            ["one two", "three", "four"]
+        );
+        assert_eq!(split_sentences("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(split_sentences("first\nsecond", 8), ["first", "second"]);
+        assert!(split_sentences("   ", 10).is_empty());
+    }
+}
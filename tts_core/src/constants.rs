@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 pub const RED: u32 = 0xff0000;
 pub const FREE_NEUTRAL_COLOUR: u32 = 0x3498db;
 pub const PREMIUM_NEUTRAL_COLOUR: u32 = 0xcaa652;
 
+/// The longest `/set xsaid_window` can be, in seconds.
+pub const MAX_XSAID_WINDOW: u16 = 60 * 60;
+
 pub const OPTION_SEPERATORS: [&str; 4] = [
     ":small_orange_diamond:",
     ":small_blue_diamond:",
@@ -9,18 +14,51 @@ pub const OPTION_SEPERATORS: [&str; 4] = [
     ":star:",
 ];
 
+pub const DEFAULT_TTS_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_TTS_READ_TIMEOUT: Duration = Duration::from_secs(20);
+pub const DEFAULT_TTS_MAX_RETRIES: u8 = 2;
+/// The delay before the first retry of a tts-service request, doubled for each following retry.
+pub const TTS_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// The size of the buffer between the tts-service download and songbird's decoder.
+pub const AUDIO_STREAM_BUFFER: usize = 64 * 1024;
+
+/// Messages this long or longer are not read, unless the server's premium tier raises the limit.
+pub const DEFAULT_MESSAGE_LENGTH_LIMIT: usize = 1500;
+
+/// The longest piece of a message synthesized in one request, see [`crate::common::split_sentences`].
+pub const TTS_CHUNK_LENGTH: usize = 300;
+
 pub const GTTS_DISABLED_ERROR: &str =
     "The `gTTS` voice mode is currently disabled due to maintenance so cannot be used.";
 
-pub const DM_WELCOME_MESSAGE: &str = "
-**All messages after this will be sent to a private channel where we can assist you.**
-**DO NOT SEND PERSONAL INFORMATION TO ANY DISCORD BOT, BOT DEVELOPERS CAN SEE THE MESSAGES.**
-Please keep in mind that we aren't always online and get a lot of messages, so if you don't get a response within a day repeat your message.
-There are some basic rules if you want to get help though:
-`1.` Ask your question, don't just ask for help
-`2.` Don't spam, troll, or send random stuff (including server invites)
-`3.` Many questions are answered in `-help`, try that first (also the default prefix is `-`)
-";
+/// Spoken names for popular websites, used alongside the names each server adds with `/set link_name`.
+pub const KNOWN_SITES: [(&str, &str); 20] = [
+    ("youtube.com", "YouTube"),
+    ("youtu.be", "YouTube"),
+    ("twitter.com", "Twitter"),
+    ("x.com", "Twitter"),
+    ("twitch.tv", "Twitch"),
+    ("reddit.com", "Reddit"),
+    ("redd.it", "Reddit"),
+    ("github.com", "GitHub"),
+    ("discord.com", "Discord"),
+    ("discord.gg", "Discord invite"),
+    ("discordapp.com", "Discord"),
+    ("tenor.com", "Tenor"),
+    ("giphy.com", "Giphy"),
+    ("spotify.com", "Spotify"),
+    ("soundcloud.com", "SoundCloud"),
+    ("wikipedia.org", "Wikipedia"),
+    ("tiktok.com", "TikTok"),
+    ("instagram.com", "Instagram"),
+    ("steampowered.com", "Steam"),
+    ("amazon.com", "Amazon"),
+];
+
+/// Filenames Discord gives to pasted or recorded files, which are not worth reading out.
+pub const GENERIC_FILENAMES: [&str; 5] = ["image", "unknown", "video", "voice message", "message"];
+pub const MAX_FILENAME_LENGTH: usize = 32;
 
 pub const DB_SETUP_QUERY: &str = "
     CREATE type TTSMode AS ENUM (
@@ -123,3 +161,105 @@ pub const DB_SETUP_QUERY: &str = "
     INSERT INTO user_voice(user_id, mode) VALUES(0, 'gtts');
     INSERT INTO guild_voice(guild_id, mode, voice) VALUES(0, 'gtts', 'en');
 ";
+
+/// The SQLite equivalent of [`DB_SETUP_QUERY`], already at the current schema as SQLite
+/// deployments never had to go through the legacy Postgres migrations.
+pub const SQLITE_DB_SETUP_QUERY: &str = "
+    CREATE TABLE userinfo (
+        user_id             bigint     PRIMARY KEY,
+        dm_blocked          bool       DEFAULT False,
+        dm_welcomed         bool       DEFAULT False,
+        bot_banned          bool       DEFAULT False,
+        use_new_formatting  bool       DEFAULT False,
+        voice_mode          text,
+        premium_voice_mode  text
+    );
+
+    CREATE TABLE guilds (
+        guild_id        bigint      PRIMARY KEY,
+        channel         bigint      DEFAULT 0,
+        premium_user    bigint,
+        required_role   bigint,
+        xsaid           bool        DEFAULT True,
+        bot_ignore      bool        DEFAULT True,
+        auto_join       bool        DEFAULT False,
+        to_translate    bool        DEFAULT False,
+        require_voice   bool        DEFAULT True,
+        text_in_voice   bool        DEFAULT True,
+        skip_emoji      bool        DEFAULT False,
+        msg_length      smallint    DEFAULT 30,
+        repeated_chars  smallint    DEFAULT 0,
+        prefix          varchar(6)  DEFAULT '-',
+        required_prefix varchar(6),
+        target_lang     varchar(5),
+        audience_ignore bool        DEFAULT True,
+        voice_mode      text        DEFAULT 'gtts',
+
+        FOREIGN KEY         (premium_user)
+        REFERENCES userinfo (user_id)
+        ON DELETE CASCADE
+    );
+
+    CREATE TABLE guild_voice (
+        guild_id      bigint,
+        mode          text,
+        voice         text     NOT NULL,
+
+        PRIMARY KEY (guild_id, mode),
+
+        FOREIGN KEY       (guild_id)
+        REFERENCES guilds (guild_id)
+        ON DELETE CASCADE
+    );
+
+    CREATE TABLE user_voice (
+        user_id       bigint,
+        mode          text,
+        voice         text,
+        speaking_rate real,
+
+        PRIMARY KEY (user_id, mode),
+
+        FOREIGN KEY         (user_id)
+        REFERENCES userinfo (user_id)
+        ON DELETE CASCADE
+    );
+
+    CREATE TABLE nicknames (
+        guild_id bigint,
+        user_id  bigint,
+        name     text,
+
+        PRIMARY KEY (guild_id, user_id),
+
+        FOREIGN KEY       (guild_id)
+        REFERENCES guilds (guild_id)
+        ON DELETE CASCADE,
+
+        FOREIGN KEY         (user_id)
+        REFERENCES userinfo (user_id)
+        ON DELETE CASCADE
+    );
+
+    CREATE TABLE analytics (
+        event          text  NOT NULL,
+        count          int   NOT NULL,
+        is_command     bool  NOT NULL,
+        date_collected date  NOT NULL DEFAULT CURRENT_DATE,
+        PRIMARY KEY (event, is_command, date_collected)
+    );
+
+    CREATE TABLE errors (
+        traceback_hash  blob    PRIMARY KEY,
+        traceback       text    NOT NULL,
+        message_id      bigint  NOT NULL,
+        occurrences     int     DEFAULT 1
+    );
+
+    INSERT INTO guilds(guild_id) VALUES(0);
+    INSERT INTO userinfo(user_id) VALUES(0);
+    INSERT INTO nicknames(guild_id, user_id) VALUES (0, 0);
+
+    INSERT INTO user_voice(user_id, mode) VALUES(0, 'gtts');
+    INSERT INTO guild_voice(guild_id, mode, voice) VALUES(0, 'gtts', 'en');
+";
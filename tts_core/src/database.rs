@@ -1,14 +1,17 @@
-use std::{hash::Hash, sync::Arc};
+use std::{fmt::Write as _, hash::Hash, sync::Arc};
 
 use dashmap::DashMap;
 use typesize::TypeSize;
 
 pub use crate::database_models::*;
-use crate::structs::{Result, TTSMode};
+use crate::{
+    storage::{Backend, Pool, Row},
+    structs::{Result, TTSMode},
+};
 
-type PgArguments<'a> = <sqlx::Postgres as sqlx::database::Database>::Arguments<'a>;
-type QueryAs<'a, R> = sqlx::query::QueryAs<'a, sqlx::Postgres, R, PgArguments<'a>>;
-type Query<'a> = sqlx::query::Query<'a, sqlx::Postgres, PgArguments<'a>>;
+type Arguments<'a> = <Backend as sqlx::database::Database>::Arguments<'a>;
+type QueryAs<'a, R> = sqlx::query::QueryAs<'a, Backend, R, Arguments<'a>>;
+type Query<'a> = sqlx::query::Query<'a, Backend, Arguments<'a>>;
 
 pub trait CacheKeyTrait: std::cmp::Eq + Hash {
     fn bind_query(self, query: Query<'_>) -> Query<'_>;
@@ -44,11 +47,28 @@ impl CacheKeyTrait for (i64, TTSMode) {
 
 type OwnedArc<T> = typesize::ptr::SizableArc<T, typesize::ptr::Owned>;
 
+trait BindValue: Send {
+    fn bind_to<'q>(self: Box<Self>, query: Query<'q>) -> Query<'q>;
+}
+
+impl<Val> BindValue for Val
+where
+    for<'a> Val: sqlx::Encode<'a, Backend>,
+    Val: sqlx::Type<Backend>,
+    Val: Send + 'static,
+{
+    fn bind_to<'q>(self: Box<Self>, query: Query<'q>) -> Query<'q> {
+        query.bind(*self)
+    }
+}
+
 pub struct Handler<CacheKey, RowT: Compact> {
-    pool: sqlx::PgPool,
+    pool: Pool,
     cache: DashMap<CacheKey, OwnedArc<RowT::Compacted>>,
 
     default_row: Arc<RowT::Compacted>,
+    table_name: &'static str,
+    id_columns: &'static [&'static str],
     single_insert: &'static str,
     create_row: &'static str,
     select: &'static str,
@@ -58,10 +78,12 @@ pub struct Handler<CacheKey, RowT: Compact> {
 impl<CacheKey, RowT> Handler<CacheKey, RowT>
 where
     CacheKey: CacheKeyTrait + Sync + Send + Copy + Default,
-    RowT: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> + Compact + Send + Unpin,
+    RowT: for<'r> sqlx::FromRow<'r, Row> + Compact + Send + Unpin,
 {
     pub async fn new(
-        pool: sqlx::PgPool,
+        pool: Pool,
+        table_name: &'static str,
+        id_columns: &'static [&'static str],
         select: &'static str,
         delete: &'static str,
         create_row: &'static str,
@@ -76,6 +98,8 @@ where
             cache: DashMap::new(),
             default_row,
             pool,
+            table_name,
+            id_columns,
             select,
             delete,
             create_row,
@@ -84,7 +108,7 @@ where
     }
 
     async fn _get(
-        pool: &sqlx::PgPool,
+        pool: &Pool,
         key: CacheKey,
         select: &'static str,
     ) -> Result<Option<Arc<RowT::Compacted>>> {
@@ -115,18 +139,40 @@ where
         Ok(())
     }
 
+    pub async fn delete(&self, identifier: CacheKey) -> Result<()> {
+        identifier
+            .bind_query(sqlx::query(self.delete))
+            .execute(&self.pool)
+            .await?;
+
+        self.invalidate_cache(&identifier);
+        Ok(())
+    }
+
+    pub fn invalidate_cache(&self, identifier: &CacheKey) {
+        self.cache.remove(identifier);
+    }
+}
+
+impl<CacheKey, RowT> Handler<CacheKey, RowT>
+where
+    CacheKey: CacheKeyTrait + Sync + Send + Copy + Default,
+    RowT: for<'r> sqlx::FromRow<'r, Row> + Compact + HasColumns + Send + Unpin,
+{
+    /// Writes a single column, use [`Self::set_many`] to write multiple at once.
     pub async fn set_one<Val>(
         &self,
         identifier: CacheKey,
-        key: &'static str,
+        column: RowT::Column,
         value: Val,
     ) -> Result<()>
     where
-        for<'a> Val: sqlx::Encode<'a, sqlx::Postgres>,
-        Val: sqlx::Type<sqlx::Postgres>,
+        for<'a> Val: sqlx::Encode<'a, Backend>,
+        Val: sqlx::Type<Backend>,
         Val: Sync + Send,
     {
-        let query_raw = self.single_insert.replace("{key}", key);
+        let column: &str = column.into();
+        let query_raw = self.single_insert.replace("{key}", column);
 
         identifier
             .bind_query(sqlx::query(&query_raw))
@@ -138,18 +184,91 @@ where
         Ok(())
     }
 
-    pub async fn delete(&self, identifier: CacheKey) -> Result<()> {
-        identifier
-            .bind_query(sqlx::query(self.delete))
-            .execute(&self.pool)
-            .await?;
+    /// Starts a write of multiple columns, performed as a single upsert by [`SetMany::execute`].
+    pub fn set_many(&self, identifier: CacheKey) -> SetMany<'_, CacheKey, RowT> {
+        SetMany {
+            handler: self,
+            identifier,
+            columns: Vec::new(),
+            values: Vec::new(),
+        }
+    }
 
-        self.invalidate_cache(&identifier);
-        Ok(())
+    fn multi_insert_query(&self, columns: &[RowT::Column]) -> String {
+        let id_columns = self.id_columns.join(", ");
+        let mut column_names = id_columns.clone();
+        let mut placeholders = String::new();
+        let mut updates = String::new();
+
+        for i in 1..=self.id_columns.len() {
+            let sep = if i == 1 { "" } else { ", " };
+            write!(placeholders, "{sep}${i}").unwrap();
+        }
+
+        for (i, column) in columns.iter().enumerate() {
+            let column: &str = (*column).into();
+            let placeholder = self.id_columns.len() + i + 1;
+            let sep = if i == 0 { "" } else { ", " };
+
+            write!(column_names, ", {column}").unwrap();
+            write!(placeholders, ", ${placeholder}").unwrap();
+            write!(updates, "{sep}{column} = EXCLUDED.{column}").unwrap();
+        }
+
+        format!(
+            "INSERT INTO {}({column_names}) VALUES ({placeholders})
+            ON CONFLICT ({id_columns}) DO UPDATE SET {updates}",
+            self.table_name
+        )
     }
+}
 
-    pub fn invalidate_cache(&self, identifier: &CacheKey) {
-        self.cache.remove(identifier);
+/// A pending write of multiple columns to a single row, created by [`Handler::set_many`].
+#[must_use = "SetMany does nothing until `execute` is called"]
+pub struct SetMany<'a, CacheKey, RowT: Compact + HasColumns> {
+    handler: &'a Handler<CacheKey, RowT>,
+    identifier: CacheKey,
+    columns: Vec<RowT::Column>,
+    values: Vec<Box<dyn BindValue>>,
+}
+
+impl<CacheKey, RowT> SetMany<'_, CacheKey, RowT>
+where
+    CacheKey: CacheKeyTrait + Sync + Send + Copy + Default,
+    RowT: for<'r> sqlx::FromRow<'r, Row> + Compact + HasColumns + Send + Unpin,
+{
+    pub fn set<Val>(mut self, column: RowT::Column, value: Val) -> Self
+    where
+        for<'a> Val: sqlx::Encode<'a, Backend>,
+        Val: sqlx::Type<Backend>,
+        Val: Send + 'static,
+    {
+        let value: Box<dyn BindValue> = Box::new(value);
+        if let Some(index) = self.columns.iter().position(|c| *c == column) {
+            self.values[index] = value;
+        } else {
+            self.columns.push(column);
+            self.values.push(value);
+        }
+
+        self
+    }
+
+    pub async fn execute(self) -> Result<()> {
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+
+        let query_raw = self.handler.multi_insert_query(&self.columns);
+        let mut query = self.identifier.bind_query(sqlx::query(&query_raw));
+        for value in self.values {
+            query = value.bind_to(query);
+        }
+
+        query.execute(&self.handler.pool).await?;
+
+        self.handler.invalidate_cache(&self.identifier);
+        Ok(())
     }
 }
 
@@ -180,6 +299,8 @@ macro_rules! create_db_handler {
 
         database::Handler::new(
             $pool,
+            TABLE_NAME,
+            &[ID_NAME],
             const_format::formatcp!("SELECT * FROM {TABLE_NAME} WHERE {ID_NAME} = $1"),
             const_format::formatcp!("DELETE FROM {TABLE_NAME} WHERE {ID_NAME} = $1"),
             const_format::formatcp!(
@@ -199,6 +320,8 @@ macro_rules! create_db_handler {
 
         database::Handler::new(
             $pool,
+            TABLE_NAME,
+            &[ID_NAME1, ID_NAME2],
             const_format::formatcp!(
                 "SELECT * FROM {TABLE_NAME} WHERE {ID_NAME1} = $1 AND {ID_NAME2} = $2"
             ),
@@ -1,11 +1,14 @@
-use std::num::NonZeroU8;
+use std::num::{NonZeroU16, NonZeroU8};
 
 use arrayvec::ArrayString;
+use strum_macros::IntoStaticStr;
 use typesize::derive::TypeSize;
 
-use poise::serenity_prelude::{ChannelId, GuildId, RoleId, UserId};
+use poise::serenity_prelude::{small_fixed_array::FixedString, ChannelId, GuildId, RoleId, UserId};
 
-use crate::structs::{IsPremium, TTSMode};
+use crate::structs::{
+    GuildPremium, LinkReadingMode, NameSource, PremiumPerk, StageReadingMode, TTSMode,
+};
 
 const MAX_VOICE_LENGTH: usize = 20;
 
@@ -26,6 +29,11 @@ pub trait Compact {
     fn compact(self) -> Self::Compacted;
 }
 
+/// Links a raw row type to the columns which can be written to, so column names are checked at compile time.
+pub trait HasColumns {
+    type Column: Copy + PartialEq + Into<&'static str> + Send;
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[derive(sqlx::FromRow)]
 pub struct GuildRowRaw {
@@ -33,6 +41,7 @@ pub struct GuildRowRaw {
     pub premium_user: Option<i64>,
     pub required_role: Option<i64>,
     pub xsaid: bool,
+    pub xsaid_reannounce: bool,
     pub auto_join: bool,
     pub bot_ignore: bool,
     pub skip_emoji: bool,
@@ -40,21 +49,75 @@ pub struct GuildRowRaw {
     pub require_voice: bool,
     pub text_in_voice: bool,
     pub audience_ignore: bool,
+    pub idle_leave_message: bool,
+    pub describe_messages: bool,
+    pub read_edits: bool,
+    pub read_threads: bool,
+    pub stage_auto_speak: bool,
+    pub announce_speakers: bool,
     pub msg_length: i16,
+    pub xsaid_window: i16,
     pub repeated_chars: i16,
+    pub idle_timeout: i16,
+    pub link_reading: i16,
+    pub stage_reading: i16,
+    pub name_source: i16,
     pub prefix: String,
     pub target_lang: Option<String>,
     pub required_prefix: Option<String>,
+    pub xsaid_template: Option<String>,
+    pub language: Option<String>,
     pub voice_mode: TTSMode,
 }
 
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum GuildColumn {
+    Channel,
+    PremiumUser,
+    RequiredRole,
+    Xsaid,
+    XsaidReannounce,
+    AutoJoin,
+    BotIgnore,
+    SkipEmoji,
+    ToTranslate,
+    RequireVoice,
+    TextInVoice,
+    AudienceIgnore,
+    IdleLeaveMessage,
+    DescribeMessages,
+    ReadEdits,
+    ReadThreads,
+    StageAutoSpeak,
+    AnnounceSpeakers,
+    MsgLength,
+    XsaidWindow,
+    RepeatedChars,
+    IdleTimeout,
+    LinkReading,
+    StageReading,
+    NameSource,
+    Prefix,
+    TargetLang,
+    RequiredPrefix,
+    XsaidTemplate,
+    Language,
+    VoiceMode,
+}
+
+impl HasColumns for GuildRowRaw {
+    type Column = GuildColumn;
+}
+
 #[bool_to_bitflags::bool_to_bitflags(owning_setters)]
-#[derive(Debug, Clone, Copy, typesize::derive::TypeSize)]
+#[derive(Debug, Clone, typesize::derive::TypeSize)]
 pub struct GuildRow {
     pub channel: Option<ChannelId>,
     pub premium_user: Option<UserId>,
     pub required_role: Option<RoleId>,
     pub xsaid: bool,
+    pub xsaid_reannounce: bool,
     pub auto_join: bool,
     pub bot_ignore: bool,
     pub skip_emoji: bool,
@@ -62,20 +125,33 @@ pub struct GuildRow {
     pub require_voice: bool,
     pub text_in_voice: bool,
     pub audience_ignore: bool,
+    pub idle_leave_message: bool,
+    pub describe_messages: bool,
+    pub read_edits: bool,
+    pub read_threads: bool,
+    pub stage_auto_speak: bool,
+    pub announce_speakers: bool,
     pub msg_length: u16,
+    pub xsaid_window: u16,
     pub repeated_chars: Option<NonZeroU8>,
+    pub idle_timeout: Option<NonZeroU16>,
+    pub link_reading: LinkReadingMode,
+    pub stage_reading: StageReadingMode,
+    pub name_source: NameSource,
     pub prefix: ArrayString<8>,
     pub target_lang: Option<ArrayString<8>>,
     pub required_prefix: Option<ArrayString<8>>,
+    pub xsaid_template: Option<FixedString<u16>>,
+    pub language: Option<ArrayString<8>>,
     pub voice_mode: TTSMode,
 }
 
 impl GuildRow {
     #[must_use]
-    pub fn target_lang(&self, is_premium: IsPremium) -> Option<&str> {
+    pub fn target_lang(&self, premium: Option<GuildPremium<'_>>) -> Option<&str> {
         if let Some(target_lang) = &self.target_lang
             && self.to_translate()
-            && is_premium.into()
+            && premium.is_some_and(|premium| premium.has_perk(PremiumPerk::Translation))
         {
             Some(target_lang.as_str())
         } else {
@@ -93,7 +169,12 @@ impl Compact for GuildRowRaw {
             premium_user: self.premium_user.map(|id| UserId::new(id as u64)),
             required_role: self.required_role.map(|id| RoleId::new(id as u64)),
             msg_length: self.msg_length as u16,
+            xsaid_window: self.xsaid_window as u16,
             repeated_chars: NonZeroU8::new(self.repeated_chars as u8),
+            idle_timeout: NonZeroU16::new(self.idle_timeout as u16),
+            link_reading: LinkReadingMode::from_i16(self.link_reading),
+            stage_reading: StageReadingMode::from_i16(self.stage_reading),
+            name_source: NameSource::from_i16(self.name_source),
             prefix: truncate_convert(self.prefix, "guild.prefix"),
             target_lang: self
                 .target_lang
@@ -101,9 +182,12 @@ impl Compact for GuildRowRaw {
             required_prefix: self
                 .required_prefix
                 .map(|t| truncate_convert(t, "guild.required_prefix")),
+            xsaid_template: self.xsaid_template.map(FixedString::from_string_trunc),
+            language: self.language.map(|l| truncate_convert(l, "guild.language")),
             voice_mode: self.voice_mode,
         }
         .set_xsaid(self.xsaid)
+        .set_xsaid_reannounce(self.xsaid_reannounce)
         .set_auto_join(self.auto_join)
         .set_bot_ignore(self.bot_ignore)
         .set_skip_emoji(self.skip_emoji)
@@ -111,10 +195,16 @@ impl Compact for GuildRowRaw {
         .set_require_voice(self.require_voice)
         .set_text_in_voice(self.text_in_voice)
         .set_audience_ignore(self.audience_ignore)
+        .set_idle_leave_message(self.idle_leave_message)
+        .set_describe_messages(self.describe_messages)
+        .set_read_edits(self.read_edits)
+        .set_read_threads(self.read_threads)
+        .set_stage_auto_speak(self.stage_auto_speak)
+        .set_announce_speakers(self.announce_speakers)
     }
 }
 
-#[derive(sqlx::FromRow, Clone, Copy)]
+#[derive(sqlx::FromRow, Clone)]
 #[expect(
     clippy::struct_excessive_bools,
     reason = "raw version of compacted type"
@@ -126,6 +216,23 @@ pub struct UserRowRaw {
     pub use_new_formatting: bool,
     pub voice_mode: Option<TTSMode>,
     pub premium_voice_mode: Option<TTSMode>,
+    pub language: Option<String>,
+}
+
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserColumn {
+    DmBlocked,
+    DmWelcomed,
+    BotBanned,
+    UseNewFormatting,
+    VoiceMode,
+    PremiumVoiceMode,
+    Language,
+}
+
+impl HasColumns for UserRowRaw {
+    type Column = UserColumn;
 }
 
 #[bool_to_bitflags::bool_to_bitflags(owning_setters)]
@@ -137,6 +244,7 @@ pub struct UserRow {
     pub use_new_formatting: bool,
     pub voice_mode: Option<TTSMode>,
     pub premium_voice_mode: Option<TTSMode>,
+    pub language: Option<ArrayString<8>>,
 }
 
 impl Compact for UserRowRaw {
@@ -145,6 +253,7 @@ impl Compact for UserRowRaw {
         Self::Compacted {
             voice_mode: self.voice_mode,
             premium_voice_mode: self.premium_voice_mode,
+            language: self.language.map(|l| truncate_convert(l, "user.language")),
             __generated_flags: UserRowGeneratedFlags::empty(),
         }
         .set_dm_blocked(self.dm_blocked)
@@ -161,6 +270,16 @@ pub struct GuildVoiceRowRaw {
     pub voice: String,
 }
 
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum GuildVoiceColumn {
+    Voice,
+}
+
+impl HasColumns for GuildVoiceRowRaw {
+    type Column = GuildVoiceColumn;
+}
+
 #[derive(Debug, Clone, Copy, TypeSize)]
 
 pub struct GuildVoiceRow {
@@ -188,6 +307,17 @@ pub struct UserVoiceRowRaw {
     pub speaking_rate: Option<f32>,
 }
 
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserVoiceColumn {
+    Voice,
+    SpeakingRate,
+}
+
+impl HasColumns for UserVoiceRowRaw {
+    type Column = UserVoiceColumn;
+}
+
 #[derive(Debug, Clone, Copy, TypeSize)]
 pub struct UserVoiceRow {
     pub user_id: Option<UserId>,
@@ -213,13 +343,69 @@ impl Compact for UserVoiceRowRaw {
 #[derive(Debug, TypeSize, sqlx::FromRow)]
 pub struct NicknameRow {
     pub name: Option<String>,
+    /// If set, only moderators can change this member's spoken name.
+    pub locked: bool,
 }
 
 pub type NicknameRowRaw = NicknameRow;
 
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum NicknameColumn {
+    Name,
+    Locked,
+}
+
+impl HasColumns for NicknameRowRaw {
+    type Column = NicknameColumn;
+}
+
 impl Compact for NicknameRowRaw {
     type Compacted = NicknameRow;
     fn compact(self) -> Self::Compacted {
         self
     }
 }
+
+#[derive(Debug, TypeSize, sqlx::FromRow)]
+pub struct VoiceAnnouncementRow {
+    pub announce_joins: bool,
+    pub announce_leaves: bool,
+    pub join_message: Option<String>,
+    pub leave_message: Option<String>,
+    pub move_message: Option<String>,
+}
+
+pub type VoiceAnnouncementRowRaw = VoiceAnnouncementRow;
+
+#[derive(IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum VoiceAnnouncementColumn {
+    AnnounceJoins,
+    AnnounceLeaves,
+    JoinMessage,
+    LeaveMessage,
+    MoveMessage,
+}
+
+impl HasColumns for VoiceAnnouncementRowRaw {
+    type Column = VoiceAnnouncementColumn;
+}
+
+impl Compact for VoiceAnnouncementRowRaw {
+    type Compacted = VoiceAnnouncementRow;
+    fn compact(self) -> Self::Compacted {
+        self
+    }
+}
+
+/// A row of the `announcements` table, which is not cached as it is only read by the scheduler.
+#[derive(Debug, sqlx::FromRow)]
+pub struct AnnouncementRow {
+    pub id: i64,
+    pub guild_id: i64,
+    pub channel_id: Option<i64>,
+    pub message: String,
+    pub schedule: Option<String>,
+    pub next_run: i64,
+}
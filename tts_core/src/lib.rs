@@ -2,12 +2,21 @@
 #![allow(async_fn_in_trait)]
 
 pub mod analytics;
+pub mod audio_stream;
+pub mod circuit_breaker;
 pub mod common;
 pub mod constants;
 pub mod database;
 pub mod database_models;
 pub mod errors;
+#[cfg(feature = "local-tts")]
+pub mod local_tts;
 pub mod macros;
+pub mod normalization;
 pub mod opt_ext;
+pub mod storage;
 pub mod structs;
 pub mod traits;
+pub mod translations;
+pub mod voices;
+pub mod xsaid_template;
@@ -0,0 +1,68 @@
+use std::{path::PathBuf, process::Stdio};
+
+use anyhow::bail;
+use tokio::{io::AsyncWriteExt as _, process::Command, sync::Semaphore};
+
+use crate::{opt_ext::OptionTryUnwrap as _, structs::Result};
+
+/// The most synthesizer processes run at once, so an outage cannot fork one per message.
+const MAX_PROCESSES: usize = 4;
+static PROCESS_PERMITS: Semaphore = Semaphore::const_new(MAX_PROCESSES);
+
+#[derive(serde::Deserialize)]
+pub struct LocalTTSConfig {
+    /// An eSpeak-NG compatible binary, called with `--stdout --stdin` and optionally `-v <voice>`.
+    pub binary: PathBuf,
+    /// The voice used when the message is not being read with an eSpeak voice.
+    pub default_voice: Option<String>,
+}
+
+/// Synthesizes `content` in-process with the configured binary, for when the tts-service is unreachable.
+pub async fn synthesize(
+    config: &LocalTTSConfig,
+    content: &str,
+    voice: Option<&str>,
+) -> Result<songbird::input::Input> {
+    let _permit = PROCESS_PERMITS.acquire().await?;
+
+    let mut command = Command::new(&config.binary);
+    command
+        .args(["--stdout", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    if let Some(voice) = voice.or(config.default_voice.as_deref()) {
+        command.args(["-v", voice]);
+    }
+
+    let mut child = command.spawn()?;
+    let mut stdin = child.stdin.take().try_unwrap()?;
+
+    // Written alongside reading the output, so a full stdout pipe cannot block the write.
+    let write_content = async move {
+        stdin.write_all(content.as_bytes()).await?;
+        drop(stdin);
+        Ok::<_, anyhow::Error>(())
+    };
+
+    let ((), output) =
+        tokio::try_join!(write_content, async { Ok(child.wait_with_output().await?) })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Local TTS exited with {}: {}", output.status, stderr.trim());
+    }
+
+    let mut hint = songbird::input::core::probe::Hint::new();
+    hint.with_extension("wav");
+
+    let input = Box::new(std::io::Cursor::new(output.stdout));
+    let wrapped_audio = songbird::input::LiveInput::Raw(songbird::input::AudioStream {
+        input,
+        hint: Some(hint),
+    });
+
+    Ok(songbird::input::Input::Live(wrapped_audio, None))
+}
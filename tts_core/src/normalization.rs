@@ -0,0 +1,594 @@
+use std::{borrow::Cow, collections::HashMap, path::Path};
+
+use anyhow::bail;
+use regex::Regex;
+
+use crate::structs::Result;
+
+const DEFAULT_RULES: &str = include_str!("../../normalization/en.toml");
+
+/// A spoken form which may differ between one and many, such as `["{amount} dollar", "{amount} dollars"]`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Forms {
+    Same(String),
+    Plural([String; 2]),
+}
+
+impl Forms {
+    fn get(&self, is_one: bool) -> &str {
+        match self {
+            Self::Same(form) => form,
+            Self::Plural([one, _]) if is_one => one,
+            Self::Plural([_, other]) => other,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct NumberWords {
+    ones: [String; 20],
+    tens: [String; 10],
+    tens_separator: String,
+    hundred: String,
+    scales: Vec<String>,
+    negative: String,
+    decimal_point: String,
+    ordinal_suffix: String,
+    ordinal_markers: Vec<String>,
+    /// Ordinals which do not just add [`Self::ordinal_suffix`] to the last word.
+    ordinals: HashMap<String, String>,
+}
+
+impl NumberWords {
+    fn digit(&self, digit: char) -> Option<&str> {
+        let digit = digit.to_digit(10)?;
+        Some(&self.ones[digit as usize])
+    }
+
+    fn below_thousand(&self, number: u64) -> String {
+        let (hundreds, rest) = (number / 100, number % 100);
+
+        let mut words = Vec::new();
+        if hundreds != 0 {
+            words.push(format!("{} {}", self.ones[hundreds as usize], self.hundred));
+        }
+
+        if rest >= 20 {
+            let (tens, ones) = ((rest / 10) as usize, (rest % 10) as usize);
+            if ones == 0 {
+                words.push(self.tens[tens].clone());
+            } else {
+                let (tens, separator, ones) =
+                    (&self.tens[tens], &self.tens_separator, &self.ones[ones]);
+                words.push(format!("{tens}{separator}{ones}"));
+            }
+        } else if rest != 0 {
+            words.push(self.ones[rest as usize].clone());
+        }
+
+        words.join(" ")
+    }
+
+    fn cardinal(&self, mut number: u64) -> Option<String> {
+        if number == 0 {
+            return Some(self.ones[0].clone());
+        }
+
+        let mut groups = Vec::new();
+        while number != 0 {
+            groups.push(number % 1000);
+            number /= 1000;
+        }
+
+        if groups.len() > self.scales.len() + 1 {
+            return None;
+        }
+
+        let mut words = Vec::new();
+        for (scale, group) in groups.into_iter().enumerate().rev() {
+            if group == 0 {
+                continue;
+            }
+
+            words.push(self.below_thousand(group));
+            if scale != 0 {
+                words.push(self.scales[scale - 1].clone());
+            }
+        }
+
+        Some(words.join(" "))
+    }
+
+    fn ordinal(&self, number: u64) -> Option<String> {
+        let mut cardinal = self.cardinal(number)?;
+
+        let mut last_word_start = cardinal.rfind(' ').map(|i| i + 1).unwrap_or(0);
+        if !self.tens_separator.is_empty()
+            && let Some(i) = cardinal.rfind(&self.tens_separator)
+        {
+            last_word_start = last_word_start.max(i + self.tens_separator.len());
+        }
+
+        let last_word = cardinal.split_off(last_word_start);
+        if let Some(ordinal) = self.ordinals.get(&last_word) {
+            cardinal.push_str(ordinal);
+        } else {
+            cardinal.push_str(&last_word);
+            cardinal.push_str(&self.ordinal_suffix);
+        }
+
+        Some(cardinal)
+    }
+
+    /// Reads a year as two pairs of digits, such as "nineteen ninety-nine", where that is natural.
+    fn year(&self, year: u64, pairs: bool) -> Option<String> {
+        let (century, rest) = (year / 100, year % 100);
+        if pairs && (11..100).contains(&century) && rest >= 10 {
+            Some(format!(
+                "{} {}",
+                self.cardinal(century)?,
+                self.cardinal(rest)?
+            ))
+        } else {
+            self.cardinal(year)
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TimeWords {
+    format: String,
+    minute_below_ten: String,
+    on_the_hour: String,
+    am: String,
+    pm: String,
+}
+
+#[derive(serde::Deserialize)]
+struct DateWords {
+    format: String,
+    months: [String; 12],
+    #[serde(default)]
+    year_pairs: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct DomainWords {
+    dot: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct Separators {
+    decimal: String,
+    thousands: String,
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Self {
+            decimal: String::from("."),
+            thousands: String::from(","),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct Rules {
+    acronyms: HashMap<String, String>,
+    emoticons: HashMap<String, String>,
+    currencies: HashMap<String, Forms>,
+    units: HashMap<String, Forms>,
+    separators: Separators,
+    numbers: Option<NumberWords>,
+    time: Option<TimeWords>,
+    date: Option<DateWords>,
+    domains: Option<DomainWords>,
+}
+
+struct Patterns {
+    number: Regex,
+    ordinal: Regex,
+    time: Regex,
+    date: Regex,
+    domain: Regex,
+}
+
+impl Patterns {
+    fn new(separators: &Separators) -> Result<Self> {
+        let decimal = regex::escape(&separators.decimal);
+        let thousands = regex::escape(&separators.thousands);
+
+        Ok(Self {
+            number: Regex::new(&format!(
+                r"^(-)?([0-9]{{1,3}}(?:{thousands}[0-9]{{3}})+|[0-9]+)(?:{decimal}([0-9]+))?$"
+            ))?,
+            ordinal: Regex::new(r"^([0-9]+)(\p{L}+)$")?,
+            time: Regex::new(r"^([0-9]{1,2}):([0-9]{2})(?i:([ap])\.?m\.?)?$")?,
+            date: Regex::new(r"^([0-9]{4})-([0-9]{2})-([0-9]{2})$")?,
+            domain: Regex::new(r"^(?:[a-z0-9-]+\.)+[a-z]{2,}$")?,
+        })
+    }
+}
+
+struct Amount<'a> {
+    spoken: Cow<'a, str>,
+    is_one: bool,
+}
+
+/// Rewrites text into a form the TTS engines read naturally for a single language,
+/// loaded from `normalization/{language}.toml`.
+pub struct Normalizer {
+    rules: Rules,
+    patterns: Patterns,
+}
+
+impl Normalizer {
+    fn parse(source: &str) -> Result<Self> {
+        let rules: Rules = toml::from_str(source)?;
+        let patterns = Patterns::new(&rules.separators)?;
+
+        Ok(Self { rules, patterns })
+    }
+
+    #[must_use]
+    pub fn normalize(&self, content: &str) -> String {
+        let mut previous_amount = None;
+        let words: Vec<_> = content
+            .split(' ')
+            .map(|word| {
+                let (normalized, amount) = self.normalize_word(word, previous_amount);
+                previous_amount = amount;
+                normalized
+            })
+            .collect();
+
+        words.join(" ")
+    }
+
+    /// Normalizes a single word, returning if it was a number to pick the form of a following unit.
+    fn normalize_word<'a>(
+        &'a self,
+        word: &'a str,
+        previous_amount: Option<bool>,
+    ) -> (Cow<'a, str>, Option<bool>) {
+        if let Some(emoticon) = self.rules.emoticons.get(word) {
+            return (Cow::Borrowed(emoticon), None);
+        }
+
+        let core = word.trim_end_matches(['.', ',', '!', '?', ';', ':']);
+        let trailing = &word[core.len()..];
+        if core.is_empty() {
+            return (Cow::Borrowed(word), None);
+        }
+
+        let with_trailing = |normalized: &str| Cow::Owned(format!("{normalized}{trailing}"));
+
+        let acronyms = &self.rules.acronyms;
+        let acronym = acronyms
+            .get(core)
+            .or_else(|| acronyms.get(&core.to_lowercase()));
+
+        if let Some(acronym) = acronym {
+            return (with_trailing(acronym), None);
+        }
+
+        if let Some(amount) = self.amount(core) {
+            return (with_trailing(&amount.spoken), Some(amount.is_one));
+        }
+
+        if let Some(is_one) = previous_amount
+            && let Some(unit) = self.rules.units.get(core)
+        {
+            return (with_trailing(unit.get(is_one)), None);
+        }
+
+        let normalized = self
+            .time(core)
+            .or_else(|| self.date(core))
+            .or_else(|| self.ordinal(core))
+            .or_else(|| self.currency(core))
+            .or_else(|| self.amount_with_unit(core))
+            .or_else(|| self.domain(core));
+
+        match normalized {
+            Some(normalized) => (with_trailing(&normalized), None),
+            None => (Cow::Borrowed(word), None),
+        }
+    }
+
+    fn amount<'a>(&self, text: &'a str) -> Option<Amount<'a>> {
+        let captures = self.patterns.number.captures(text)?;
+        let Some(numbers) = &self.rules.numbers else {
+            return Some(Amount {
+                spoken: Cow::Borrowed(text),
+                is_one: text == "1",
+            });
+        };
+
+        let negative = captures.get(1).is_some();
+        let decimals = captures.get(3);
+
+        let integer = captures[2].replace(&self.rules.separators.thousands, "");
+        if integer.len() > 1 && integer.starts_with('0') && !negative && decimals.is_none() {
+            // Phone numbers and codes, which are read digit by digit
+            let digits = integer
+                .chars()
+                .map(|d| numbers.digit(d))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(Amount {
+                spoken: Cow::Owned(digits.join(" ")),
+                is_one: false,
+            });
+        }
+
+        let integer: u64 = integer.parse().ok()?;
+
+        let mut spoken = String::new();
+        if negative {
+            spoken.push_str(&numbers.negative);
+            spoken.push(' ');
+        }
+
+        spoken.push_str(&numbers.cardinal(integer)?);
+        if let Some(decimals) = decimals {
+            spoken.push(' ');
+            spoken.push_str(&numbers.decimal_point);
+            for digit in decimals.as_str().chars() {
+                spoken.push(' ');
+                spoken.push_str(numbers.digit(digit)?);
+            }
+        }
+
+        let is_one = !negative && integer == 1 && decimals.is_none();
+        Some(Amount {
+            spoken: Cow::Owned(spoken),
+            is_one,
+        })
+    }
+
+    fn time(&self, text: &str) -> Option<String> {
+        let (numbers, time) = (self.rules.numbers.as_ref()?, self.rules.time.as_ref()?);
+        let captures = self.patterns.time.captures(text)?;
+
+        let hour: u64 = captures[1].parse().ok()?;
+        let minute: u64 = captures[2].parse().ok()?;
+        if hour >= 24 || minute >= 60 {
+            return None;
+        }
+
+        let template = match minute {
+            0 => &time.on_the_hour,
+            1..10 => &time.minute_below_ten,
+            _ => &time.format,
+        };
+
+        let mut spoken = template
+            .replace("{hour}", &numbers.cardinal(hour)?)
+            .replace("{minute}", &numbers.cardinal(minute)?);
+
+        if let Some(period) = captures.get(3) {
+            let is_am = period.as_str().eq_ignore_ascii_case("a");
+            spoken.push(' ');
+            spoken.push_str(if is_am { &time.am } else { &time.pm });
+        }
+
+        Some(spoken)
+    }
+
+    fn date(&self, text: &str) -> Option<String> {
+        let (numbers, date) = (self.rules.numbers.as_ref()?, self.rules.date.as_ref()?);
+        let captures = self.patterns.date.captures(text)?;
+
+        let year: u64 = captures[1].parse().ok()?;
+        let month: usize = captures[2].parse().ok()?;
+        let day: u64 = captures[3].parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let spoken = date
+            .format
+            .replace("{year}", &numbers.year(year, date.year_pairs)?)
+            .replace("{month}", &date.months[month - 1])
+            .replace("{day}", &numbers.ordinal(day)?);
+
+        Some(spoken)
+    }
+
+    fn ordinal(&self, text: &str) -> Option<String> {
+        let numbers = self.rules.numbers.as_ref()?;
+        let captures = self.patterns.ordinal.captures(text)?;
+
+        let marker = &captures[2];
+        let is_marker = |m: &String| m.eq_ignore_ascii_case(marker);
+        if !numbers.ordinal_markers.iter().any(is_marker) {
+            return None;
+        }
+
+        numbers.ordinal(captures[1].parse().ok()?)
+    }
+
+    fn currency(&self, text: &str) -> Option<String> {
+        self.rules.currencies.iter().find_map(|(symbol, forms)| {
+            let amount = text
+                .strip_prefix(symbol.as_str())
+                .or_else(|| text.strip_suffix(symbol.as_str()))?;
+
+            let amount = self.amount(amount)?;
+            Some(forms.get(amount.is_one).replace("{amount}", &amount.spoken))
+        })
+    }
+
+    fn amount_with_unit(&self, text: &str) -> Option<String> {
+        let unit_start = text.find(|c: char| !(c.is_ascii_digit() || "-.,".contains(c)))?;
+        let (amount, unit) = text.split_at(unit_start);
+
+        let unit = self.rules.units.get(unit)?;
+        let amount = self.amount(amount)?;
+        Some(format!("{} {}", amount.spoken, unit.get(amount.is_one)))
+    }
+
+    fn domain(&self, text: &str) -> Option<String> {
+        let domains = self.rules.domains.as_ref()?;
+        if !self.patterns.domain.is_match(text) {
+            return None;
+        }
+
+        Some(text.replace('.', &format!(" {} ", domains.dot)))
+    }
+}
+
+/// The [`Normalizer`] for each language with rules, keyed by ISO 639-1 language code.
+pub struct Normalizers {
+    languages: HashMap<String, Normalizer>,
+}
+
+impl Normalizers {
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self> {
+        let mut languages = HashMap::new();
+        languages.insert(String::from("en"), Normalizer::parse(DEFAULT_RULES)?);
+
+        let directory = directory.as_ref();
+        if !directory.exists() {
+            return Ok(Self { languages });
+        }
+
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            let normalizer = match Normalizer::parse(&std::fs::read_to_string(&path)?) {
+                Ok(normalizer) => normalizer,
+                Err(err) => bail!("Failed to load {}: {err}", path.display()),
+            };
+
+            languages.insert(language.to_lowercase(), normalizer);
+        }
+
+        Ok(Self { languages })
+    }
+
+    /// Finds the rules for a voice's language, such as `en-US` or `en`.
+    #[must_use]
+    pub fn get(&self, language_code: &str) -> Option<&Normalizer> {
+        let language = language_code.split(['-', '_', ' ']).next()?;
+        self.languages.get(&language.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Normalizer;
+
+    fn load(source: &str) -> Normalizer {
+        Normalizer::parse(source).expect("normalization rules should parse")
+    }
+
+    #[test]
+    fn english() {
+        let en = load(include_str!("../../normalization/en.toml"));
+
+        assert_eq!(en.normalize("21"), "twenty-one");
+        assert_eq!(en.normalize("1,250"), "one thousand two hundred fifty");
+        assert_eq!(en.normalize("-3.14"), "minus three point one four");
+        assert_eq!(en.normalize("call 0123"), "call zero one two three");
+
+        assert_eq!(en.normalize("1st"), "first");
+        assert_eq!(en.normalize("22nd"), "twenty-second");
+        assert_eq!(en.normalize("100th"), "one hundredth");
+
+        assert_eq!(en.normalize("9:00"), "nine o'clock");
+        assert_eq!(en.normalize("9:05pm"), "nine oh five PM");
+        assert_eq!(en.normalize("13:45"), "thirteen forty-five");
+
+        assert_eq!(
+            en.normalize("1999-12-31"),
+            "December thirty-first, nineteen ninety-nine"
+        );
+        assert_eq!(en.normalize("2000-01-02"), "January second, two thousand");
+
+        assert_eq!(en.normalize("$1"), "one dollar");
+        assert_eq!(en.normalize("5€"), "five euros");
+
+        assert_eq!(en.normalize("1 km"), "one kilometre");
+        assert_eq!(en.normalize("5kg"), "five kilograms");
+        assert_eq!(en.normalize("50%"), "fifty percent");
+
+        assert_eq!(
+            en.normalize("brb, IIRC"),
+            "be right back, if I recall correctly"
+        );
+        assert_eq!(en.normalize("example.com"), "example dot com");
+    }
+
+    #[test]
+    fn german() {
+        let de = load(include_str!("../../normalization/de.toml"));
+
+        // German has no number words, so numbers are left for the TTS engine to read.
+        assert_eq!(de.normalize("1.000"), "1.000");
+        assert_eq!(de.normalize("3,5"), "3,5");
+
+        assert_eq!(de.normalize("5€"), "5 Euro");
+        assert_eq!(de.normalize("£1"), "1 Pfund");
+        assert_eq!(de.normalize("10 km"), "10 Kilometer");
+        assert_eq!(de.normalize("20°C"), "20 Grad Celsius");
+        assert_eq!(de.normalize("zb"), "zum Beispiel");
+        assert_eq!(de.normalize("beispiel.de"), "beispiel Punkt de");
+    }
+
+    #[test]
+    fn french() {
+        let fr = load(include_str!("../../normalization/fr.toml"));
+
+        assert_eq!(fr.normalize("2,5"), "2,5");
+        assert_eq!(fr.normalize("5€"), "5 euros");
+        assert_eq!(fr.normalize("$3"), "3 dollars");
+        assert_eq!(fr.normalize("3 km"), "3 kilomètres");
+        assert_eq!(fr.normalize("50%"), "50 pour cent");
+        assert_eq!(fr.normalize("mdr"), "mort de rire");
+        assert_eq!(fr.normalize("exemple.fr"), "exemple point fr");
+    }
+
+    #[test]
+    fn spanish() {
+        let es = load(include_str!("../../normalization/es.toml"));
+
+        assert_eq!(es.normalize("1.000"), "1.000");
+        assert_eq!(es.normalize("1€"), "1 euro");
+        assert_eq!(es.normalize("$2"), "2 dólares");
+        assert_eq!(es.normalize("1 km"), "1 kilómetro");
+        assert_eq!(es.normalize("2 kg"), "2 kilogramos");
+        assert_eq!(es.normalize("pq"), "porque");
+        assert_eq!(es.normalize("ejemplo.es"), "ejemplo punto es");
+    }
+
+    #[test]
+    fn non_ascii_digits() {
+        let en = load(include_str!("../../normalization/en.toml"));
+
+        // Arabic-Indic and full width digits are not read as numbers, rather than panicking.
+        for text in [
+            "٠١٢٣",
+            "0١٢",
+            "3.١٤",
+            "-1.٥",
+            "０１２",
+            "١st",
+            "1٢:30",
+            "2024-0١-01",
+            "$٥",
+        ] {
+            assert_eq!(en.normalize(text), text);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::structs::{PostgresConfig, Result};
+
+#[cfg(not(feature = "sqlite"))]
+pub type Backend = sqlx::Postgres;
+#[cfg(feature = "sqlite")]
+pub type Backend = sqlx::Sqlite;
+
+pub type Pool = sqlx::Pool<Backend>;
+pub type Row = <Backend as sqlx::Database>::Row;
+pub type Transaction<'a> = sqlx::Transaction<'a, Backend>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    Sqlite,
+}
+
+/// A database which the bot can store its data in, picked at compile time with the `sqlite` feature.
+pub trait StorageBackend: sqlx::Database {
+    const DIALECT: Dialect;
+    /// The section of `config.toml` which is deserialized into [`Self::Config`].
+    const CONFIG_SECTION: &'static str;
+
+    type Config: serde::de::DeserializeOwned;
+
+    async fn connect(config: Self::Config) -> Result<sqlx::Pool<Self>>;
+
+    /// Pushes a condition onto `query` which checks that the preceding column is one of `values`.
+    fn push_any_of<'a>(query: &mut sqlx::QueryBuilder<'a, Self>, values: &'a [i64]);
+}
+
+impl StorageBackend for sqlx::Postgres {
+    const DIALECT: Dialect = Dialect::Postgres;
+    const CONFIG_SECTION: &'static str = "PostgreSQL-Info";
+
+    type Config = PostgresConfig;
+
+    async fn connect(config: Self::Config) -> Result<sqlx::Pool<Self>> {
+        let pool_config = sqlx::postgres::PgPoolOptions::new();
+        let pool_config = if let Some(max_connections) = config.max_connections {
+            pool_config.max_connections(max_connections)
+        } else {
+            pool_config
+        };
+
+        let pool_options = sqlx::postgres::PgConnectOptions::new()
+            .host(&config.host)
+            .username(&config.user)
+            .database(&config.database)
+            .password(&config.password);
+
+        Ok(pool_config.connect_with(pool_options).await?)
+    }
+
+    fn push_any_of<'a>(query: &mut sqlx::QueryBuilder<'a, Self>, values: &'a [i64]) {
+        query.push("= ANY(").push_bind(values).push(")");
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl StorageBackend for sqlx::Sqlite {
+    const DIALECT: Dialect = Dialect::Sqlite;
+    const CONFIG_SECTION: &'static str = "SQLite-Info";
+
+    type Config = crate::structs::SqliteConfig;
+
+    async fn connect(config: Self::Config) -> Result<sqlx::Pool<Self>> {
+        let pool_options = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(&config.path)
+            .create_if_missing(true)
+            .foreign_keys(true);
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect_with(pool_options)
+            .await?;
+
+        Ok(pool)
+    }
+
+    fn push_any_of<'a>(query: &mut sqlx::QueryBuilder<'a, Self>, values: &'a [i64]) {
+        // SQLite has no array type to bind, so each value gets its own parameter.
+        query.push("IN (");
+        let mut separated = query.separated(", ");
+        for value in values {
+            separated.push_bind(*value);
+        }
+        separated.push_unseparated(")");
+    }
+}
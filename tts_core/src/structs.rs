@@ -1,15 +1,17 @@
 use std::{
     borrow::Cow,
-    collections::BTreeMap,
+    collections::HashMap,
     num::NonZeroU8,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, OnceLock,
     },
+    time::{Duration, Instant},
 };
 
 use aformat::{aformat, ArrayString, CapStr};
 pub use anyhow::{Error, Result};
+use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use parking_lot::Mutex;
 use serde::Deserialize as _;
@@ -20,10 +22,20 @@ use typesize::derive::TypeSize;
 use poise::serenity_prelude::{
     self as serenity,
     small_fixed_array::{FixedArray, FixedString},
-    ChannelId, GuildId, RoleId, SkuId, UserId,
+    ChannelId, GuildId, MessageId, RoleId, SkuId, UserId,
 };
 
-use crate::{analytics, bool_enum, common::timestamp_in_future, database};
+use crate::{
+    analytics,
+    circuit_breaker::CircuitBreakers,
+    common::timestamp_in_future,
+    constants::{DEFAULT_MESSAGE_LENGTH_LIMIT, MAX_XSAID_WINDOW},
+    database,
+    database_models::{GuildColumn, UserColumn},
+    normalization::{Normalizer, Normalizers},
+    translations::Translations,
+    voices::VoiceLists,
+};
 
 macro_rules! into_static_display {
     ($struct:ident, max_length($len:literal)) => {
@@ -56,6 +68,11 @@ pub struct Config {
     pub premium: Option<PremiumConfig>,
     #[serde(rename = "Bot-List-Tokens")]
     pub bot_list_tokens: Option<BotListTokens>,
+    #[serde(rename = "Dashboard-API")]
+    pub dashboard: Option<DashboardConfig>,
+    #[cfg(feature = "local-tts")]
+    #[serde(rename = "Local-TTS")]
+    pub local_tts: Option<crate::local_tts::LocalTTSConfig>,
 }
 
 #[derive(serde::Deserialize)]
@@ -67,6 +84,14 @@ pub struct MainConfig {
     pub proxy_url: Option<FixedString>,
     pub invite_channel: ChannelId,
     pub tts_service: reqwest::Url,
+    /// Extra tts-service instances, audio requests are spread across these and `tts_service`.
+    #[serde(default)]
+    pub tts_service_failover: Vec<reqwest::Url>,
+    pub tts_service_connect_timeout_secs: Option<u64>,
+    pub tts_service_read_timeout_secs: Option<u64>,
+    pub tts_service_max_retries: Option<u8>,
+    /// If set, a second instance is asked for audio when the first has not responded in time.
+    pub tts_service_hedge_after_ms: Option<u64>,
     pub token: serenity::Token,
     pub main_server: GuildId,
     pub ofs_role: RoleId,
@@ -74,6 +99,9 @@ pub struct MainConfig {
     // Only for situations where gTTS has broken
     #[serde(default)]
     pub gtts_disabled: AtomicBool,
+    /// The mode to read messages with while another mode's circuit breaker is open, before eSpeak.
+    #[serde(default)]
+    pub fallback_mode: Option<TTSMode>,
 }
 
 #[derive(serde::Deserialize)]
@@ -85,6 +113,16 @@ pub struct PostgresConfig {
     pub max_connections: Option<u32>,
 }
 
+#[derive(serde::Deserialize)]
+pub struct SqliteConfig {
+    pub path: std::path::PathBuf,
+}
+
+#[derive(serde::Deserialize)]
+pub struct DashboardConfig {
+    pub bind_address: std::net::SocketAddr,
+}
+
 #[derive(serde::Deserialize)]
 pub struct WebsiteInfo {
     pub url: reqwest::Url,
@@ -110,8 +148,123 @@ pub struct PremiumConfig {
     pub discord_monetisation_enabled: Option<bool>,
     pub patreon_page_url: ArrayString<64>,
     pub patreon_service: reqwest::Url,
-    pub basic_sku: SkuId,
-    pub extra_sku: SkuId,
+    /// The tiers that can be subscribed to, from `[[Premium-Info.tiers]]`.
+    #[serde(default)]
+    pub tiers: Vec<PremiumTier>,
+    /// Replaced by `tiers`, turned into the tiers they used to grant by [`Self::load_legacy_skus`].
+    pub basic_sku: Option<SkuId>,
+    pub extra_sku: Option<SkuId>,
+}
+
+impl PremiumConfig {
+    /// Converts the `basic_sku` and `extra_sku` from before `tiers` existed, then checks
+    /// that Discord monetisation has tiers to grant.
+    pub fn load_legacy_skus(&mut self) -> Result<()> {
+        let legacy_skus = [
+            ("Basic", self.basic_sku.take(), 2),
+            ("Extra", self.extra_sku.take(), 5),
+        ];
+
+        let has_legacy_skus = legacy_skus.iter().any(|(_, sku, _)| sku.is_some());
+        if has_legacy_skus && !self.tiers.is_empty() {
+            anyhow::bail!(
+                "Set either [[Premium-Info.tiers]] or `basic_sku` and `extra_sku`, not both"
+            );
+        }
+
+        for (name, sku, servers) in legacy_skus {
+            let Some(sku) = sku else { continue };
+            self.tiers.push(PremiumTier {
+                name: FixedString::from_static_trunc(name),
+                sku: Some(sku),
+                servers: NonZeroU8::new(servers).unwrap(),
+                modes: PremiumTier::default_modes(),
+                message_length_limit: None,
+                perks: vec![PremiumPerk::Translation],
+            });
+        }
+
+        if self.discord_monetisation_enabled == Some(true) && self.tiers.is_empty() {
+            anyhow::bail!(
+                "`discord_monetisation_enabled` is set, but [[Premium-Info.tiers]] has no tiers to grant"
+            );
+        }
+
+        Ok(())
+    }
+
+    fn tier_for_sku(&self, sku_id: SkuId) -> Option<u8> {
+        let index = self
+            .tiers
+            .iter()
+            .position(|tier| tier.sku == Some(sku_id))?;
+        u8::try_from(index).ok()
+    }
+
+    /// Patreon only reports a server count, so members get the largest tier it covers.
+    fn tier_for_patreon(&self, entitled_servers: NonZeroU8) -> Option<u8> {
+        let tiers = self.tiers.iter().enumerate();
+        let (index, _) = tiers
+            .clone()
+            .filter(|(_, tier)| tier.servers <= entitled_servers)
+            .max_by_key(|(_, tier)| tier.servers)
+            .or_else(|| tiers.min_by_key(|(_, tier)| tier.servers))?;
+
+        u8::try_from(index).ok()
+    }
+}
+
+/// Something other than voice modes that a premium tier can unlock.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PremiumPerk {
+    /// `/set translation` and `/set translation_lang`.
+    Translation,
+}
+
+/// A level of TTS Bot Premium, set with `[[Premium-Info.tiers]]`.
+#[derive(serde::Deserialize)]
+pub struct PremiumTier {
+    pub name: FixedString,
+    /// The Discord SKU which grants this tier, if it is sold through Discord.
+    pub sku: Option<SkuId>,
+    /// How many servers can be activated with `/premium_activate`.
+    pub servers: NonZeroU8,
+    /// The premium modes activated servers can use, the first is switched to on activation.
+    #[serde(default = "PremiumTier::default_modes")]
+    pub modes: Vec<TTSMode>,
+    /// Replaces [`DEFAULT_MESSAGE_LENGTH_LIMIT`] in activated servers.
+    pub message_length_limit: Option<u16>,
+    #[serde(default)]
+    pub perks: Vec<PremiumPerk>,
+}
+
+impl PremiumTier {
+    fn default_modes() -> Vec<TTSMode> {
+        vec![TTSMode::gCloud, TTSMode::Polly]
+    }
+}
+
+/// The premium features of a server, from the tier of the user who activated it.
+#[derive(Clone, Copy)]
+pub struct GuildPremium<'a> {
+    /// `None` if no tiers are configured, such as when self-hosting, which unlocks everything.
+    pub tier: Option<&'a PremiumTier>,
+}
+
+impl GuildPremium<'_> {
+    #[must_use]
+    pub fn has_perk(self, perk: PremiumPerk) -> bool {
+        self.tier.is_none_or(|tier| tier.perks.contains(&perk))
+    }
+
+    #[must_use]
+    pub fn message_length_limit(self) -> usize {
+        self.tier
+            .and_then(|tier| tier.message_length_limit)
+            .map(usize::from)
+            .unwrap_or(DEFAULT_MESSAGE_LENGTH_LIMIT)
+    }
 }
 
 pub struct WebhookConfig {
@@ -133,24 +286,28 @@ impl JoinVCToken {
     }
 }
 
-bool_enum!(IsPremium(No | Yes));
-
 #[derive(Clone, Copy)]
 pub enum FailurePoint {
     NotSubscribed(UserId),
     PremiumUser,
+    /// The server is premium, but its tier does not include this perk.
+    MissingPerk(PremiumPerk),
     Guild,
 }
 
 #[derive(serde::Deserialize, Clone, Copy)]
 pub struct PremiumInfo {
     pub entitled_servers: NonZeroU8,
+    /// The index of the tier in [`PremiumConfig::tiers`], `None` unlocks everything.
+    #[serde(skip)]
+    pub tier: Option<u8>,
 }
 
 impl PremiumInfo {
     fn fake() -> Self {
         Self {
             entitled_servers: NonZeroU8::MAX,
+            tier: None,
         }
     }
 }
@@ -182,6 +339,7 @@ pub struct RegexCache {
     pub id_in_brackets: regex::Regex,
     pub emoji_captures: regex::Regex,
     pub emoji_filter: regex::Regex,
+    pub unreadable_name_chars: regex::Regex,
 }
 
 impl RegexCache {
@@ -198,13 +356,102 @@ impl RegexCache {
             id_in_brackets: regex::Regex::new(r"\((\d+)\)")?,
             emoji_captures: regex::Regex::new(r"<(a?):([^<>]+):\d+>")?,
             emoji_filter: regex::Regex::new(r"(?s:<a?:[^<>]+:\d+>)|\p{Emoji}")?,
+            unreadable_name_chars: regex::Regex::new(r"[^\p{L}\p{N}\p{M}\p{Zs}'.-]+")?,
             bot_mention: OnceLock::new(),
         })
     }
 }
 
-#[derive(Clone, Copy)]
-pub struct LastXsaidInfo(UserId, std::time::SystemTime);
+/// A message which has been queued to be read, kept so it can be dropped if deleted or edited.
+#[derive(Clone)]
+pub struct QueuedMessage {
+    pub channel_id: ChannelId,
+    /// One track per sentence chunk, in the order they are read.
+    pub tracks: Arc<[songbird::tracks::TrackHandle]>,
+    pub content: FixedString<u16>,
+    pub queued_at: Instant,
+}
+
+impl QueuedMessage {
+    /// Stops every chunk of the message, returning if any had not finished yet.
+    pub fn stop(&self) -> bool {
+        let mut stopped = false;
+        for track in self.tracks.iter() {
+            stopped |= track.stop().is_ok();
+        }
+
+        stopped
+    }
+}
+
+/// The messages queued to be read in each guild, so lookups only have to search a single queue.
+#[derive(Default)]
+pub struct QueuedMessages(DashMap<GuildId, Vec<(MessageId, QueuedMessage)>>);
+
+impl QueuedMessages {
+    /// How long a message is kept for, by which point it has almost certainly been read.
+    const LIFETIME: Duration = Duration::from_secs(60 * 10);
+
+    pub fn insert(&self, guild_id: GuildId, message_id: MessageId, message: QueuedMessage) {
+        let mut queue = self.0.entry(guild_id).or_default();
+        queue.retain(|(_, queued)| queued.queued_at.elapsed() <= Self::LIFETIME);
+        queue.push((message_id, message));
+    }
+
+    #[must_use]
+    pub fn get(&self, guild_id: GuildId, message_id: MessageId) -> Option<QueuedMessage> {
+        let queue = self.0.get(&guild_id)?;
+        let (_, message) = queue.iter().find(|(id, _)| *id == message_id)?;
+        Some(message.clone())
+    }
+
+    pub fn remove(&self, guild_id: GuildId, message_id: MessageId) -> Option<QueuedMessage> {
+        let mut queue = self.0.get_mut(&guild_id)?;
+        let index = queue.iter().position(|(id, _)| *id == message_id)?;
+        Some(queue.remove(index).1)
+    }
+
+    /// Removes the message which `track` is one of the chunks of.
+    pub fn remove_by_track(
+        &self,
+        guild_id: GuildId,
+        track: &songbird::tracks::TrackHandle,
+    ) -> Option<QueuedMessage> {
+        let mut queue = self.0.get_mut(&guild_id)?;
+        let index = queue
+            .iter()
+            .position(|(_, message)| message.tracks.iter().any(|t| t.uuid() == track.uuid()))?;
+
+        Some(queue.remove(index).1)
+    }
+
+    /// Removes every message sent in `channel_id`, such as when a thread is closed.
+    pub fn remove_channel(&self, guild_id: GuildId, channel_id: ChannelId) -> Vec<QueuedMessage> {
+        let Some(mut queue) = self.0.get_mut(&guild_id) else {
+            return Vec::new();
+        };
+
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut *queue)
+            .into_iter()
+            .partition(|(_, message)| message.channel_id == channel_id);
+
+        *queue = kept;
+        removed.into_iter().map(|(_, message)| message).collect()
+    }
+
+    /// Forgets every message in a guild, such as when the bot leaves voice.
+    pub fn remove_guild(&self, guild_id: GuildId) {
+        self.0.remove(&guild_id);
+    }
+}
+
+/// Who has been read in a voice channel, used to decide whether to say the author's name again.
+#[derive(Default)]
+pub struct LastXsaidInfo {
+    last_author: Option<UserId>,
+    /// When each author last had a message read in this channel.
+    last_read: HashMap<UserId, Instant>,
+}
 
 impl LastXsaidInfo {
     fn get_vc_member_count(guild: &serenity::Guild, channel_id: ChannelId) -> usize {
@@ -218,25 +465,64 @@ impl LastXsaidInfo {
     }
 
     #[must_use]
-    pub fn new(user: UserId) -> Self {
-        Self(user, std::time::SystemTime::now())
-    }
+    fn should_announce_name(
+        &self,
+        guild: &serenity::Guild,
+        channel_id: ChannelId,
+        user: UserId,
+        window: Duration,
+        reannounce: bool,
+    ) -> bool {
+        if reannounce && self.last_author != Some(user) {
+            return true;
+        }
 
-    #[must_use]
-    pub fn should_announce_name(&self, guild: &serenity::Guild, user: UserId) -> bool {
-        let Some(voice_channel_id) = guild.voice_states.get(&user).and_then(|v| v.channel_id)
-        else {
+        let Some(last_read) = self.last_read.get(&user) else {
             return true;
         };
 
-        if user != self.0 {
-            return true;
-        }
+        let window_passed = last_read.elapsed() > window;
+        let is_only_author = Self::get_vc_member_count(guild, channel_id) <= 1;
+
+        window_passed || is_only_author
+    }
+}
+
+/// Tracks [`LastXsaidInfo`] for each voice channel the bot reads into.
+#[derive(Default)]
+pub struct LastToXsaidTracker(DashMap<(GuildId, ChannelId), LastXsaidInfo>);
+
+impl LastToXsaidTracker {
+    /// Checks if `user`'s name should be said, with the window and reannounce settings of the guild.
+    #[must_use]
+    pub fn should_announce_name(
+        &self,
+        guild: &serenity::Guild,
+        channel_id: ChannelId,
+        user: UserId,
+        window: Duration,
+        reannounce: bool,
+    ) -> bool {
+        self.0.get(&(guild.id, channel_id)).is_none_or(|info| {
+            info.should_announce_name(guild, channel_id, user, window, reannounce)
+        })
+    }
+
+    pub fn record(&self, guild_id: GuildId, channel_id: ChannelId, user: UserId) {
+        let mut info = self.0.entry((guild_id, channel_id)).or_default();
+        info.last_author = Some(user);
 
-        let has_been_min = self.1.elapsed().unwrap().as_secs() > 60;
-        let is_only_author = Self::get_vc_member_count(guild, voice_channel_id) <= 1;
+        // Nobody can be within a window longer than the maximum, so forget them.
+        let max_window = Duration::from_secs(MAX_XSAID_WINDOW.into());
+        info.last_read
+            .retain(|_, last_read| last_read.elapsed() <= max_window);
+        info.last_read.insert(user, Instant::now());
+    }
 
-        has_been_min || is_only_author
+    /// Forgets every channel in a guild, such as when the bot leaves voice.
+    pub fn remove_guild(&self, guild_id: GuildId) {
+        self.0
+            .retain(|(channel_guild_id, _), _| *channel_guild_id != guild_id);
     }
 }
 
@@ -247,19 +533,28 @@ pub struct Data {
     pub nickname_db: database::Handler<[i64; 2], database::NicknameRowRaw>,
     pub user_voice_db: database::Handler<(i64, TTSMode), database::UserVoiceRowRaw>,
     pub guild_voice_db: database::Handler<(i64, TTSMode), database::GuildVoiceRowRaw>,
+    pub voice_announcements_db: database::Handler<i64, database::VoiceAnnouncementRowRaw>,
 
     pub entitlement_cache: mini_moka::sync::Cache<UserId, CachedEntitlement>,
+    pub voice_announcement_cooldowns: mini_moka::sync::Cache<(GuildId, UserId), ()>,
+    pub link_names_cache: mini_moka::sync::Cache<GuildId, Arc<HashMap<String, String>>>,
+    pub queued_messages: QueuedMessages,
     pub join_vc_tokens: DashMap<GuildId, Arc<tokio::sync::Mutex<()>>>,
     pub last_to_xsaid_tracker: LastToXsaidTracker,
+    pub last_voice_activity: DashMap<GuildId, Instant>,
     pub startup_message: serenity::MessageId,
     pub premium_avatar_url: FixedString<u16>,
     pub system_info: Mutex<sysinfo::System>,
     pub start_time: std::time::SystemTime,
     pub songbird: Arc<songbird::Songbird>,
     pub reqwest: reqwest::Client,
+    /// A client with the tts-service timeouts applied, only to be used for the tts-service.
+    pub tts_reqwest: reqwest::Client,
     pub regex_cache: RegexCache,
+    pub translations: Translations,
+    pub normalizers: Normalizers,
     pub webhooks: WebhookConfig,
-    pub pool: sqlx::PgPool,
+    pub pool: crate::storage::Pool,
 
     pub config: MainConfig,
     pub premium_config: Option<PremiumConfig>,
@@ -270,12 +565,10 @@ pub struct Data {
     pub fully_started: std::sync::atomic::AtomicBool,
     pub update_startup_lock: tokio::sync::Mutex<()>,
 
-    pub espeak_voices: FixedArray<FixedString<u8>>,
-    pub gtts_voices: BTreeMap<FixedString<u8>, FixedString<u8>>,
-    pub polly_voices: BTreeMap<FixedString<u8>, PollyVoice>,
-    pub gcloud_voices: BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>>,
-
-    pub translation_languages: BTreeMap<FixedString<u8>, FixedString<u8>>,
+    pub voices: ArcSwap<VoiceLists>,
+    pub circuit_breakers: CircuitBreakers,
+    #[cfg(feature = "local-tts")]
+    pub local_tts: Option<crate::local_tts::LocalTTSConfig>,
 }
 
 impl std::fmt::Debug for Data {
@@ -298,14 +591,73 @@ impl Data {
         })
     }
 
+    /// Fetches the spoken names a server has set for link domains with `/set link_name`.
+    pub async fn link_names(&self, guild_id: GuildId) -> Result<Arc<HashMap<String, String>>> {
+        if let Some(link_names) = self.link_names_cache.get(&guild_id) {
+            return Ok(link_names);
+        }
+
+        let link_names: Vec<(String, String)> =
+            sqlx::query_as("SELECT domain, name FROM link_names WHERE guild_id = $1")
+                .bind(guild_id.get() as i64)
+                .fetch_all(&self.pool)
+                .await?;
+
+        let link_names = Arc::new(link_names.into_iter().collect::<HashMap<_, _>>());
+        self.link_names_cache.insert(guild_id, link_names.clone());
+        Ok(link_names)
+    }
+
+    /// Finds the text normalization rules for the language of a voice.
+    #[must_use]
+    pub fn normalizer(&self, voice: &str, mode: TTSMode) -> Option<&Normalizer> {
+        let language = match mode {
+            TTSMode::Polly => {
+                let voices = self.voices.load();
+                Cow::Owned(voices.polly_voices.get(voice)?.language_code.to_string())
+            }
+            TTSMode::gTTS | TTSMode::eSpeak | TTSMode::gCloud => Cow::Borrowed(voice),
+        };
+
+        self.normalizers.get(&language)
+    }
+
+    /// Picks the interface language, preferring the user's setting, then the server's, then Discord's locale.
+    pub async fn locale(
+        &self,
+        user_id: Option<UserId>,
+        guild_id: Option<GuildId>,
+        discord_locale: Option<&str>,
+    ) -> Result<&str> {
+        if let Some(user_id) = user_id
+            && let Some(language) = self.userinfo_db.get(user_id.into()).await?.language
+            && let Some(locale) = self.translations.resolve(&language)
+        {
+            return Ok(locale);
+        }
+
+        if let Some(guild_id) = guild_id
+            && let Some(language) = self.guilds_db.get(guild_id.into()).await?.language
+            && let Some(locale) = self.translations.resolve(&language)
+        {
+            return Ok(locale);
+        }
+
+        let locale = discord_locale.and_then(|locale| self.translations.resolve(locale));
+        Ok(locale.unwrap_or(crate::translations::DEFAULT_LOCALE))
+    }
+
     async fn fetch_patreon_info(&self, user_id: UserId) -> Result<Option<PremiumInfo>> {
         if let Some(config) = &self.premium_config {
             let mut url = config.patreon_service.clone();
             url.set_path(&aformat!("/members/{user_id}"));
 
             let req = self.reqwest.get(url);
-            let resp = req.send().await?.error_for_status()?.json().await?;
-            Ok(resp)
+            let resp: Option<PremiumInfo> = req.send().await?.error_for_status()?.json().await?;
+            Ok(resp.map(|info| PremiumInfo {
+                tier: config.tier_for_patreon(info.entitled_servers),
+                ..info
+            }))
         } else {
             // Return fake PatreonInfo if `patreon_service` has not been set to simplify self-hosting.
             Ok(Some(PremiumInfo::fake()))
@@ -340,15 +692,14 @@ impl Data {
             .into_iter()
             .find(|entitlement| entitlement.ends_at.is_none_or(timestamp_in_future))
         {
-            let entitled_servers = if entitlement.sku_id == premium_config.extra_sku {
-                NonZeroU8::new(5).unwrap()
-            } else if entitlement.sku_id == premium_config.basic_sku {
-                NonZeroU8::new(2).unwrap()
-            } else {
+            let Some(tier) = premium_config.tier_for_sku(entitlement.sku_id) else {
                 anyhow::bail!("Found unknown entitlement sku: {}", entitlement.sku_id);
             };
 
-            let premium_info = PremiumInfo { entitled_servers };
+            let premium_info = PremiumInfo {
+                entitled_servers: premium_config.tiers[usize::from(tier)].servers,
+                tier: Some(tier),
+            };
             match entitlement.ends_at {
                 Some(expiry) => CachedEntitlement::Normal(premium_info, expiry),
                 None => CachedEntitlement::NoExpiry(premium_info),
@@ -381,36 +732,100 @@ impl Data {
         Ok(None)
     }
 
+    /// Looks up the configured tier a subscription is for.
+    #[must_use]
+    pub fn premium_tier(&self, premium_info: PremiumInfo) -> Option<&PremiumTier> {
+        let tiers = &self.premium_config.as_ref()?.tiers;
+        tiers.get(usize::from(premium_info.tier?))
+    }
+
     pub async fn premium_check(
         &self,
         http: &serenity::Http,
         guild_id: Option<GuildId>,
-    ) -> Result<Option<FailurePoint>> {
+    ) -> Result<Result<GuildPremium<'_>, FailurePoint>> {
         let Some(guild_id) = guild_id else {
-            return Ok(Some(FailurePoint::Guild));
+            return Ok(Err(FailurePoint::Guild));
         };
 
         let guild_row = self.guilds_db.get(guild_id.get() as i64).await?;
         let Some(premium_user) = guild_row.premium_user else {
-            return Ok(Some(FailurePoint::PremiumUser));
+            return Ok(Err(FailurePoint::PremiumUser));
         };
 
-        if self.fetch_premium_info(http, premium_user).await?.is_some() {
-            Ok(None)
-        } else {
-            Ok(Some(FailurePoint::PremiumUser))
+        match self.fetch_premium_info(http, premium_user).await? {
+            Some(premium_info) => Ok(Ok(GuildPremium {
+                tier: self.premium_tier(premium_info),
+            })),
+            None => Ok(Err(FailurePoint::PremiumUser)),
         }
     }
 
+    /// Fetches the premium features of a server, or `None` if it is not premium.
+    pub async fn guild_premium(
+        &self,
+        http: &serenity::Http,
+        guild_id: GuildId,
+    ) -> Result<Option<GuildPremium<'_>>> {
+        let guild_id = Some(guild_id);
+        self.premium_check(http, guild_id).await.map(Result::ok)
+    }
+
     pub async fn is_premium_simple(
         &self,
         http: &serenity::Http,
         guild_id: GuildId,
     ) -> Result<bool> {
-        let guild_id = Some(guild_id);
-        self.premium_check(http, guild_id)
+        self.guild_premium(http, guild_id)
             .await
-            .map(|o| o.is_none())
+            .map(|premium| premium.is_some())
+    }
+
+    /// Picks a mode to use instead of `mode` if its circuit breaker is open.
+    #[must_use]
+    pub fn fallback_mode(
+        &self,
+        mode: TTSMode,
+        premium: Option<GuildPremium<'_>>,
+    ) -> Option<TTSMode> {
+        if self.circuit_breakers.allow(mode) {
+            return None;
+        }
+
+        let candidates = self
+            .config
+            .fallback_mode
+            .into_iter()
+            .chain([TTSMode::eSpeak]);
+        candidates
+            .filter(|fallback| *fallback != mode && fallback.is_unlocked(premium))
+            .find(|fallback| self.circuit_breakers.allow(*fallback))
+    }
+
+    /// Falls back to the mode's default voice if `voice` has been removed from the tts-service,
+    /// without resetting the saved setting in case it comes back.
+    fn available_voice(
+        &self,
+        mode: TTSMode,
+        voice: Option<Cow<'static, str>>,
+    ) -> Cow<'static, str> {
+        let voices = self.voices.load();
+        voice
+            .filter(|voice| !voices.has_voices(mode) || voices.contains(mode, voice))
+            .unwrap_or_else(|| Cow::Borrowed(mode.default_voice()))
+    }
+
+    /// Gets the language to translate to, ignoring it if removed from the tts-service.
+    #[must_use]
+    pub fn target_lang<'a>(
+        &self,
+        guild_row: &'a database::GuildRow,
+        premium: Option<GuildPremium<'_>>,
+    ) -> Option<&'a str> {
+        let languages = &self.voices.load().translation_languages;
+        guild_row
+            .target_lang(premium)
+            .filter(|lang| languages.is_empty() || languages.contains_key(*lang))
     }
 
     pub async fn parse_user_or_guild(
@@ -420,7 +835,7 @@ impl Data {
         guild_id: Option<GuildId>,
     ) -> Result<(Cow<'static, str>, TTSMode)> {
         let info = if let Some(guild_id) = guild_id {
-            Some((guild_id, self.is_premium_simple(http, guild_id).await?))
+            Some((guild_id, self.guild_premium(http, guild_id).await?))
         } else {
             None
         };
@@ -431,17 +846,17 @@ impl Data {
     pub async fn parse_user_or_guild_with_premium(
         &self,
         author_id: UserId,
-        guild_info: Option<(GuildId, bool)>,
+        guild_info: Option<(GuildId, Option<GuildPremium<'_>>)>,
     ) -> Result<(Cow<'static, str>, TTSMode)> {
         let user_row = self.userinfo_db.get(author_id.into()).await?;
-        let (guild_id, guild_is_premium) = match guild_info {
+        let (guild_id, premium) = match guild_info {
             Some((id, p)) => (Some(id), p),
-            None => (None, false),
+            None => (None, None),
         };
 
         let mut guild_row = None;
         let mut mode = {
-            let user_mode = if guild_is_premium {
+            let user_mode = if premium.is_some() {
                 user_row.premium_voice_mode
             } else {
                 user_row.voice_mode
@@ -461,7 +876,7 @@ impl Data {
             mode = TTSMode::eSpeak;
         }
 
-        if mode.is_premium() && !guild_is_premium {
+        if !mode.is_unlocked(premium) {
             mode = TTSMode::default();
 
             if user_row.voice_mode.is_some_and(TTSMode::is_premium) {
@@ -469,7 +884,7 @@ impl Data {
                     "User ID {author_id}'s normal voice mode is set to a premium mode! Resetting."
                 );
                 self.userinfo_db
-                    .set_one(author_id.into(), "voice_mode", mode)
+                    .set_one(author_id.into(), UserColumn::VoiceMode, mode)
                     .await?;
             } else if let Some(guild_id) = guild_id
                 && let Some(guild_row) = guild_row
@@ -477,7 +892,7 @@ impl Data {
             {
                 warn!("Guild ID {guild_id}'s voice mode is set to a premium mode without being premium! Resetting.");
                 self.guilds_db
-                    .set_one(guild_id.into(), "voice_mode", mode)
+                    .set_one(guild_id.into(), GuildColumn::VoiceMode, mode)
                     .await?;
             } else {
                 warn!("Guild {guild_id:?} - User {author_id} has a mode set to premium without being premium!");
@@ -499,9 +914,35 @@ impl Data {
                 }
             } else {
                 None
-            }.unwrap_or_else(|| Cow::Borrowed(mode.default_voice()));
+            };
+
+        Ok((self.available_voice(mode, voice), mode))
+    }
+
+    /// Gets the guild's default voice and mode, for speech not coming from a user.
+    pub async fn parse_guild_with_premium(
+        &self,
+        guild_id: GuildId,
+        premium: Option<GuildPremium<'_>>,
+    ) -> Result<(Cow<'static, str>, TTSMode)> {
+        let guild_row = self.guilds_db.get(guild_id.into()).await?;
+
+        let mut mode = guild_row.voice_mode;
+        if self.config.gtts_disabled.load(Ordering::Relaxed) && mode == TTSMode::gTTS {
+            mode = TTSMode::eSpeak;
+        }
+
+        if !mode.is_unlocked(premium) {
+            mode = TTSMode::default();
+        }
 
-        Ok((voice, mode))
+        let guild_voice_row = self.guild_voice_db.get((guild_id.into(), mode)).await?;
+        let voice = guild_voice_row
+            .guild_id
+            .is_some()
+            .then(|| Cow::Owned(guild_voice_row.voice.as_str().to_owned()));
+
+        Ok((self.available_voice(mode, voice), mode))
     }
 }
 
@@ -530,10 +971,23 @@ impl SpeakingRateInfo {
     }
 }
 
-#[derive(IntoStaticStr, sqlx::Type, TypeSize, Debug, Default, Hash, PartialEq, Eq, Copy, Clone)]
+#[derive(
+    IntoStaticStr,
+    sqlx::Type,
+    TypeSize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+)]
 #[allow(non_camel_case_types)]
 #[sqlx(rename_all = "lowercase")]
 #[sqlx(type_name = "ttsmode")]
+#[serde(rename_all = "lowercase")]
 pub enum TTSMode {
     #[default]
     gTTS,
@@ -551,6 +1005,16 @@ impl TTSMode {
         }
     }
 
+    /// If this mode can be used in a server, where `premium` is `None` if it is not premium.
+    #[must_use]
+    pub fn is_unlocked(self, premium: Option<GuildPremium<'_>>) -> bool {
+        if !self.is_premium() {
+            return true;
+        }
+
+        premium.is_some_and(|premium| premium.tier.is_none_or(|tier| tier.modes.contains(&self)))
+    }
+
     #[must_use]
     pub const fn default_voice(self) -> &'static str {
         match self {
@@ -574,6 +1038,173 @@ impl TTSMode {
 
 into_static_display!(TTSMode, max_length(6));
 
+#[derive(poise::ChoiceParameter, Clone, Copy)]
+pub enum VoiceAnnouncementKind {
+    #[name = "Member joined the voice channel"]
+    #[name = "join"]
+    Join,
+    #[name = "Member left the voice channel"]
+    #[name = "leave"]
+    Leave,
+    #[name = "Member moved into the voice channel"]
+    #[name = "move"]
+    Move,
+}
+
+impl VoiceAnnouncementKind {
+    #[must_use]
+    pub const fn column(self) -> database::VoiceAnnouncementColumn {
+        match self {
+            Self::Join => database::VoiceAnnouncementColumn::JoinMessage,
+            Self::Leave => database::VoiceAnnouncementColumn::LeaveMessage,
+            Self::Move => database::VoiceAnnouncementColumn::MoveMessage,
+        }
+    }
+
+    #[must_use]
+    pub const fn default_message(self) -> &'static str {
+        match self {
+            Self::Join => "{name} joined",
+            Self::Leave => "{name} left",
+            Self::Move => "{name} moved in",
+        }
+    }
+
+    /// The template to announce with, where `{name}` is replaced with the member's name.
+    #[must_use]
+    pub fn message(self, row: &database::VoiceAnnouncementRow) -> &str {
+        let custom = match self {
+            Self::Join => &row.join_message,
+            Self::Leave => &row.leave_message,
+            Self::Move => &row.move_message,
+        };
+
+        custom.as_deref().unwrap_or(self.default_message())
+    }
+}
+
+/// How links in messages are read out, stored as a smallint in `guilds.link_reading`.
+#[derive(
+    poise::ChoiceParameter,
+    IntoStaticStr,
+    TypeSize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LinkReadingMode {
+    #[name = "Only say that a link was sent (default)"]
+    #[name = "drop"]
+    #[default]
+    Drop,
+    #[name = "Say the domain of the link"]
+    #[name = "domain"]
+    Domain,
+    #[name = "Say the name of known websites"]
+    #[name = "known_sites"]
+    KnownSites,
+}
+
+impl LinkReadingMode {
+    #[must_use]
+    pub fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::Domain,
+            2 => Self::KnownSites,
+            _ => Self::Drop,
+        }
+    }
+}
+
+/// Who is read out in stage channels, stored as a smallint in `guilds.stage_reading`.
+#[derive(
+    poise::ChoiceParameter,
+    IntoStaticStr,
+    TypeSize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StageReadingMode {
+    #[name = "Everyone, unless audience_ignore is on (default)"]
+    #[name = "everyone"]
+    #[default]
+    Everyone,
+    #[name = "Only speakers invited to the stage"]
+    #[name = "speakers"]
+    Speakers,
+    #[name = "Only stage moderators"]
+    #[name = "moderators"]
+    Moderators,
+}
+
+impl StageReadingMode {
+    #[must_use]
+    pub fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::Speakers,
+            2 => Self::Moderators,
+            _ => Self::Everyone,
+        }
+    }
+}
+
+/// Which of a member's names is said, stored as a smallint in `guilds.name_source`.
+///
+/// Spoken names set with `/set nick` are always preferred.
+#[derive(
+    poise::ChoiceParameter,
+    IntoStaticStr,
+    TypeSize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NameSource {
+    #[name = "Server nickname, then display name (default)"]
+    #[name = "nickname"]
+    #[default]
+    Nickname,
+    #[name = "Display name, ignoring server nicknames"]
+    #[name = "display_name"]
+    DisplayName,
+    #[name = "Username"]
+    #[name = "username"]
+    Username,
+}
+
+impl NameSource {
+    #[must_use]
+    pub fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::DisplayName,
+            2 => Self::Username,
+            _ => Self::Nickname,
+        }
+    }
+}
+
 #[derive(poise::ChoiceParameter, Clone, Copy)]
 #[allow(non_camel_case_types)]
 pub enum TTSModeChoice {
@@ -665,6 +1296,12 @@ impl TTSServiceErrorCode {
     pub const fn should_ignore(self) -> bool {
         matches!(self, Self::AudioTooLong)
     }
+
+    /// If the error may go away by making the same request again, instead of being caused by the request.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Unknown)
+    }
 }
 
 #[must_use]
@@ -689,5 +1326,4 @@ pub type ApplicationContext<'a> = poise::ApplicationContext<'a, Data, CommandErr
 
 pub type CommandError = Error;
 pub type CommandResult<E = Error> = Result<(), E>;
-pub type LastToXsaidTracker = DashMap<GuildId, LastXsaidInfo>;
 pub type FrameworkContext<'a> = poise::FrameworkContext<'a, Data, CommandError>;
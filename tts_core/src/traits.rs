@@ -7,7 +7,7 @@ use crate::{
     constants::{FREE_NEUTRAL_COLOUR, PREMIUM_NEUTRAL_COLOUR},
     opt_ext::OptionTryUnwrap,
     require_guild,
-    structs::{Context, JoinVCToken, Result, TTSMode},
+    structs::{Context, Data, JoinVCToken, Result, TTSMode},
 };
 
 pub trait PoiseContextExt<'ctx> {
@@ -21,6 +21,7 @@ pub trait PoiseContextExt<'ctx> {
     ) -> Result<poise::ReplyHandle<'ctx>>;
 
     async fn neutral_colour(&self) -> u32;
+    async fn language(&self) -> Result<&'ctx str>;
     fn author_vc(&self) -> Option<serenity::ChannelId>;
     fn author_permissions(&self) -> Result<serenity::Permissions>;
 }
@@ -44,6 +45,22 @@ impl<'ctx> PoiseContextExt<'ctx> for Context<'ctx> {
         FREE_NEUTRAL_COLOUR
     }
 
+    async fn language(&self) -> Result<&'ctx str> {
+        let data = self.serenity_context().data_ref::<Data>();
+
+        let guild_locale;
+        let discord_locale = match self.locale() {
+            Some(locale) => Some(locale),
+            None => {
+                guild_locale = self.guild().map(|g| g.preferred_locale.to_string());
+                guild_locale.as_deref()
+            }
+        };
+
+        data.locale(Some(self.author().id), self.guild_id(), discord_locale)
+            .await
+    }
+
     fn author_permissions(&self) -> Result<serenity::Permissions> {
         match self {
             poise::Context::Application(poise::ApplicationContext { interaction, .. }) => {
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::bail;
+
+use crate::structs::{Command, Result};
+
+pub const DEFAULT_LOCALE: &str = "en-US";
+const DEFAULT_CATALOG: &str = include_str!("../../translations/en-US.toml");
+
+type Catalog = HashMap<String, String>;
+
+/// Message catalogs for each supported interface language, keyed by Discord locale.
+///
+/// The `en-US` catalog is built in, other catalogs are loaded from `{locale}.toml` files
+/// and fall back to `en-US` for any missing keys.
+pub struct Translations {
+    catalogs: HashMap<String, Catalog>,
+}
+
+impl Translations {
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self> {
+        let mut catalogs = HashMap::new();
+        catalogs.insert(DEFAULT_LOCALE.to_owned(), parse_catalog(DEFAULT_CATALOG)?);
+
+        let directory = directory.as_ref();
+        if !directory.exists() {
+            return Ok(Self { catalogs });
+        }
+
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+
+            let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            if locale.len() > 8 {
+                bail!("{} is not a valid Discord locale", path.display());
+            }
+
+            let catalog = parse_catalog(&std::fs::read_to_string(&path)?)?;
+            catalogs
+                .entry(locale.to_owned())
+                .or_default()
+                .extend(catalog);
+        }
+
+        Ok(Self { catalogs })
+    }
+
+    /// Finds the closest supported locale, falling back to another locale with the same language.
+    #[must_use]
+    pub fn resolve(&self, locale: &str) -> Option<&str> {
+        if let Some((locale, _)) = self.catalogs.get_key_value(locale) {
+            return Some(locale);
+        }
+
+        let language = locale.split('-').next()?;
+        let mut candidates: Vec<_> = self
+            .catalogs
+            .keys()
+            .filter(|l| l.split('-').next() == Some(language))
+            .collect();
+
+        candidates.sort_unstable();
+        candidates.first().map(|l| l.as_str())
+    }
+
+    /// Looks up a message, falling back to `en-US` then the key itself if it is missing.
+    #[must_use]
+    pub fn get<'a>(&'a self, locale: &str, key: &'a str) -> &'a str {
+        self.catalogs
+            .get(locale)
+            .and_then(|catalog| catalog.get(key))
+            .or_else(|| self.catalogs[DEFAULT_LOCALE].get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// The supported locales, along with the name of the language in that language.
+    pub fn languages(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.catalogs.keys().map(|locale| {
+            let name = self.get(locale, "language_name");
+            (locale.as_str(), name)
+        })
+    }
+
+    /// Registers the slash command name and description localizations from the
+    /// `commands.{qualified_name}.name` and `commands.{qualified_name}.description` keys.
+    pub fn localize_commands(&self, commands: &mut [Command]) {
+        for command in commands {
+            let qualified_name = command.name.to_string();
+            self.localize_command(command, &qualified_name);
+        }
+    }
+
+    fn localize_command(&self, command: &mut Command, qualified_name: &str) {
+        let name_key = format!("commands.{qualified_name}.name");
+        let description_key = format!("commands.{qualified_name}.description");
+
+        for (locale, catalog) in &self.catalogs {
+            if locale == DEFAULT_LOCALE {
+                continue;
+            }
+
+            if let Some(name) = catalog.get(&name_key) {
+                let localization = (locale.clone().into(), name.clone().into());
+                command.name_localizations.to_mut().push(localization);
+            }
+
+            if let Some(description) = catalog.get(&description_key) {
+                let localization = (locale.clone().into(), description.clone().into());
+                command
+                    .description_localizations
+                    .to_mut()
+                    .push(localization);
+            }
+        }
+
+        for subcommand in &mut command.subcommands {
+            let qualified_name = format!("{qualified_name}.{}", subcommand.name);
+            self.localize_command(subcommand, &qualified_name);
+        }
+    }
+}
+
+fn parse_catalog(source: &str) -> Result<Catalog> {
+    let mut catalog = Catalog::new();
+    flatten_table(&mut catalog, "", source.parse()?)?;
+    Ok(catalog)
+}
+
+fn flatten_table(catalog: &mut Catalog, prefix: &str, table: toml::Table) -> Result<()> {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match value {
+            toml::Value::String(message) => {
+                catalog.insert(key, message);
+            }
+            toml::Value::Table(table) => flatten_table(catalog, &key, table)?,
+            _ => bail!("Translation key {key} must be a string or a table"),
+        }
+    }
+
+    Ok(())
+}
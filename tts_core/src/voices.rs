@@ -0,0 +1,274 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use anyhow::bail;
+use tracing::info;
+
+use poise::serenity_prelude::small_fixed_array::{FixedArray, FixedString, TruncatingInto as _};
+
+use crate::structs::{Data, GoogleGender, GoogleVoice, PollyVoice, Result, TTSMode};
+
+const MODES: [TTSMode; 4] = [
+    TTSMode::gTTS,
+    TTSMode::eSpeak,
+    TTSMode::Polly,
+    TTSMode::gCloud,
+];
+
+/// The voices and translation languages supported by the tts-service, swapped out as a whole on reload.
+pub struct VoiceLists {
+    pub espeak_voices: FixedArray<FixedString<u8>>,
+    pub gtts_voices: BTreeMap<FixedString<u8>, FixedString<u8>>,
+    pub polly_voices: BTreeMap<FixedString<u8>, PollyVoice>,
+    pub gcloud_voices: BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>>,
+
+    pub translation_languages: BTreeMap<FixedString<u8>, FixedString<u8>>,
+}
+
+impl VoiceLists {
+    pub async fn fetch(
+        reqwest: &reqwest::Client,
+        tts_service: &reqwest::Url,
+        auth_key: Option<&str>,
+    ) -> Result<Self> {
+        let (gtts_voices, espeak_voices, gcloud_voices, polly_voices, translation_languages) = tokio::try_join!(
+            fetch_voices(reqwest, tts_service.clone(), auth_key, TTSMode::gTTS),
+            fetch_voices(reqwest, tts_service.clone(), auth_key, TTSMode::eSpeak),
+            fetch_voices(reqwest, tts_service.clone(), auth_key, TTSMode::gCloud),
+            fetch_voices::<Vec<PollyVoice>>(reqwest, tts_service.clone(), auth_key, TTSMode::Polly),
+            fetch_translation_languages(reqwest, tts_service.clone(), auth_key),
+        )?;
+
+        Ok(Self {
+            gtts_voices,
+            espeak_voices,
+            translation_languages,
+            gcloud_voices: prepare_gcloud_voices(gcloud_voices),
+            polly_voices: polly_voices
+                .into_iter()
+                .map(|v| (v.id.clone(), v))
+                .collect(),
+        })
+    }
+
+    /// Lists without any voices, replaced by the next successful reload.
+    #[cfg(feature = "local-tts")]
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            espeak_voices: FixedArray::empty(),
+            gtts_voices: BTreeMap::new(),
+            polly_voices: BTreeMap::new(),
+            gcloud_voices: BTreeMap::new(),
+            translation_languages: BTreeMap::new(),
+        }
+    }
+
+    /// Checks if the tts-service offers `voice` for `mode`, in the form it is saved to the database.
+    #[must_use]
+    pub fn contains(&self, mode: TTSMode, voice: &str) -> bool {
+        match mode {
+            TTSMode::gTTS => self.gtts_voices.contains_key(voice),
+            TTSMode::eSpeak => self.espeak_voices.iter().any(|v| v.as_str() == voice),
+            TTSMode::Polly => self.polly_voices.contains_key(voice),
+            TTSMode::gCloud => voice.split_once(' ').is_some_and(|(language, variant)| {
+                self.gcloud_voices
+                    .get(language)
+                    .is_some_and(|variants| variants.contains_key(variant))
+            }),
+        }
+    }
+
+    /// Checks if any voices have been loaded for `mode`, which may not be the case before the
+    /// tts-service first responds.
+    #[must_use]
+    pub fn has_voices(&self, mode: TTSMode) -> bool {
+        match mode {
+            TTSMode::gTTS => !self.gtts_voices.is_empty(),
+            TTSMode::eSpeak => !self.espeak_voices.is_empty(),
+            TTSMode::Polly => !self.polly_voices.is_empty(),
+            TTSMode::gCloud => !self.gcloud_voices.is_empty(),
+        }
+    }
+
+    /// The voice IDs for a mode, in the form they are saved to the database.
+    fn voice_ids(&self, mode: TTSMode) -> BTreeSet<String> {
+        match mode {
+            TTSMode::gTTS => self.gtts_voices.keys().map(ToString::to_string).collect(),
+            TTSMode::eSpeak => self.espeak_voices.iter().map(ToString::to_string).collect(),
+            TTSMode::Polly => self.polly_voices.keys().map(ToString::to_string).collect(),
+            TTSMode::gCloud => self
+                .gcloud_voices
+                .iter()
+                .flat_map(|(language, variants)| {
+                    variants
+                        .keys()
+                        .map(move |variant| format!("{language} {variant}"))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The changes between two [`VoiceLists`], used to log reloads.
+#[derive(Default)]
+pub struct VoiceListsDiff {
+    pub added_voices: Vec<(TTSMode, String)>,
+    pub removed_voices: Vec<(TTSMode, String)>,
+    pub added_languages: Vec<String>,
+    pub removed_languages: Vec<String>,
+}
+
+impl VoiceListsDiff {
+    fn new(old: &VoiceLists, new: &VoiceLists) -> Self {
+        let mut diff = Self::default();
+        for mode in MODES {
+            let (old_ids, new_ids) = (old.voice_ids(mode), new.voice_ids(mode));
+
+            let added = new_ids.difference(&old_ids).map(|id| (mode, id.clone()));
+            let removed = old_ids.difference(&new_ids).map(|id| (mode, id.clone()));
+
+            diff.added_voices.extend(added);
+            diff.removed_voices.extend(removed);
+        }
+
+        let old_langs: BTreeSet<_> = old.translation_languages.keys().collect();
+        let new_langs: BTreeSet<_> = new.translation_languages.keys().collect();
+
+        let added = new_langs.difference(&old_langs).map(ToString::to_string);
+        let removed = old_langs.difference(&new_langs).map(ToString::to_string);
+
+        diff.added_languages.extend(added);
+        diff.removed_languages.extend(removed);
+        diff
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_voices.is_empty()
+            && self.removed_voices.is_empty()
+            && self.added_languages.is_empty()
+            && self.removed_languages.is_empty()
+    }
+
+    fn log(&self) {
+        for (mode, voice) in &self.added_voices {
+            info!("Voice reload: Added {mode} voice {voice}");
+        }
+        for (mode, voice) in &self.removed_voices {
+            info!("Voice reload: Removed {mode} voice {voice}");
+        }
+        for language in &self.added_languages {
+            info!("Voice reload: Added translation language {language}");
+        }
+        for language in &self.removed_languages {
+            info!("Voice reload: Removed translation language {language}");
+        }
+    }
+}
+
+/// Refetches the voice lists and swaps them into [`Data`].
+///
+/// Saved voices and translation languages which have been removed are left alone, in case they
+/// come back, and fall back to the defaults when read instead.
+pub async fn reload(data: &Data) -> Result<VoiceListsDiff> {
+    let auth_key = data.config.tts_service_auth_key.as_deref();
+    let new = VoiceLists::fetch(&data.tts_reqwest, &data.config.tts_service, auth_key).await?;
+
+    let old = data.voices.load();
+    for mode in MODES {
+        // An empty list is almost certainly the tts-service failing, not every voice being removed.
+        if !new.has_voices(mode) && old.has_voices(mode) {
+            bail!("tts-service returned no voices for {mode}, refusing to reload");
+        }
+    }
+
+    let diff = VoiceListsDiff::new(&old, &new);
+    drop(old);
+
+    data.voices.store(Arc::new(new));
+    if diff.is_empty() {
+        return Ok(diff);
+    }
+
+    diff.log();
+    Ok(diff)
+}
+
+async fn fetch_json<T>(reqwest: &reqwest::Client, url: reqwest::Url, auth_header: &str) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let resp = reqwest
+        .get(url)
+        .header("Authorization", auth_header)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(resp)
+}
+
+async fn fetch_voices<T: serde::de::DeserializeOwned>(
+    reqwest: &reqwest::Client,
+    mut tts_service: reqwest::Url,
+    auth_key: Option<&str>,
+    mode: TTSMode,
+) -> Result<T> {
+    tts_service.set_path("voices");
+    tts_service
+        .query_pairs_mut()
+        .append_pair("mode", mode.into())
+        .append_pair("raw", "true")
+        .finish();
+
+    fetch_json(reqwest, tts_service, auth_key.unwrap_or("")).await
+}
+
+async fn fetch_translation_languages(
+    reqwest: &reqwest::Client,
+    mut tts_service: reqwest::Url,
+    auth_key: Option<&str>,
+) -> Result<BTreeMap<FixedString<u8>, FixedString<u8>>> {
+    tts_service.set_path("translation_languages");
+
+    let raw_langs: Vec<(String, FixedString<u8>)> =
+        fetch_json(reqwest, tts_service, auth_key.unwrap_or("")).await?;
+
+    let lang_map = raw_langs.into_iter().map(|(mut lang, name)| {
+        lang.make_ascii_lowercase();
+        (lang.trunc_into(), name)
+    });
+
+    Ok(lang_map.collect())
+}
+
+fn prepare_gcloud_voices(
+    raw_map: Vec<GoogleVoice>,
+) -> BTreeMap<FixedString<u8>, BTreeMap<FixedString<u8>, GoogleGender>> {
+    // {lang_accent: {variant: gender}}
+    let mut cleaned_map = BTreeMap::new();
+    for gvoice in raw_map {
+        let variant = gvoice
+            .name
+            .splitn(3, '-')
+            .nth(2)
+            .and_then(|mode_variant| mode_variant.split_once('-'))
+            .filter(|(mode, _)| *mode == "Standard")
+            .map(|(_, variant)| variant);
+
+        if let Some(variant) = variant {
+            let [language] = gvoice.language_codes;
+            cleaned_map
+                .entry(language)
+                .or_insert_with(BTreeMap::new)
+                .insert(FixedString::from_str_trunc(variant), gvoice.ssml_gender);
+        }
+    }
+
+    cleaned_map
+}
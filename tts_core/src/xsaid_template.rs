@@ -0,0 +1,359 @@
+use std::fmt;
+
+pub const MAX_TEMPLATE_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    Name,
+    Message,
+    Link,
+    Attachment,
+    Reply,
+}
+
+impl Placeholder {
+    pub const ALL: [Self; 5] = [
+        Self::Name,
+        Self::Message,
+        Self::Link,
+        Self::Attachment,
+        Self::Reply,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Message => "message",
+            Self::Link => "link",
+            Self::Attachment => "attachment",
+            Self::Reply => "reply",
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Part {
+    Text(String),
+    Placeholder(Placeholder),
+    /// Only said if every placeholder inside has a value.
+    Section(Vec<Part>),
+}
+
+#[derive(Debug)]
+pub enum TemplateError {
+    TooLong,
+    UnknownPlaceholder(String),
+    UnclosedPlaceholder,
+    UnclosedSection,
+    NestedSection,
+    UnexpectedClose(char),
+    MissingMessage,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong => write!(
+                f,
+                "Templates cannot be longer than {MAX_TEMPLATE_LENGTH} characters"
+            ),
+            Self::UnknownPlaceholder(name) => write!(
+                f,
+                "`{{{name}}}` is not a placeholder, use one of {}",
+                Placeholder::ALL
+                    .map(|p| format!("`{{{}}}`", p.name()))
+                    .join(", ")
+            ),
+            Self::UnclosedPlaceholder => f.write_str("A `{` is missing its closing `}`"),
+            Self::UnclosedSection => f.write_str("A `[` is missing its closing `]`"),
+            Self::NestedSection => f.write_str("Optional `[...]` sections cannot be nested"),
+            Self::UnexpectedClose(char) => write!(f, "A `{char}` has nothing to close"),
+            Self::MissingMessage => {
+                f.write_str("Templates must include `{message}` outside of `[...]` sections")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// The values to fill a template with, where `None` or empty values are left out.
+#[derive(Default)]
+pub struct TemplateValues<'a> {
+    pub name: Option<&'a str>,
+    pub message: Option<&'a str>,
+    pub link: Option<&'a str>,
+    pub attachment: Option<&'a str>,
+    pub reply: Option<&'a str>,
+}
+
+impl TemplateValues<'_> {
+    fn get(&self, placeholder: Placeholder) -> Option<&str> {
+        let value = match placeholder {
+            Placeholder::Name => self.name,
+            Placeholder::Message => self.message,
+            Placeholder::Link => self.link,
+            Placeholder::Attachment => self.attachment,
+            Placeholder::Reply => self.reply,
+        };
+
+        value.filter(|v| !v.trim().is_empty())
+    }
+}
+
+/// A server's custom phrasing for messages, set with `/set xsaid_template`.
+///
+/// `{placeholder}`s are replaced with parts of the message, and text in `[...]` is only
+/// said if every placeholder inside it has a value, such as `{name}[ replying to {reply}] said {message}`.
+#[derive(Debug)]
+pub struct XsaidTemplate {
+    parts: Vec<Part>,
+    requires_name: bool,
+}
+
+impl XsaidTemplate {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        if source.chars().count() > MAX_TEMPLATE_LENGTH {
+            return Err(TemplateError::TooLong);
+        }
+
+        let mut parts = Vec::new();
+        let mut section: Option<Vec<Part>> = None;
+        let mut text = String::new();
+        let mut has_message = false;
+        let mut requires_name = false;
+
+        let mut chars = source.chars();
+        while let Some(char) = chars.next() {
+            match char {
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(char) => name.push(char),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+
+                    let placeholder = Placeholder::from_name(name.trim())
+                        .ok_or(TemplateError::UnknownPlaceholder(name))?;
+
+                    has_message |= section.is_none() && placeholder == Placeholder::Message;
+                    requires_name |= section.is_none() && placeholder == Placeholder::Name;
+
+                    let current = section.as_mut().unwrap_or(&mut parts);
+                    push_text(current, &mut text);
+                    current.push(Part::Placeholder(placeholder));
+                }
+                '[' => {
+                    if section.is_some() {
+                        return Err(TemplateError::NestedSection);
+                    }
+
+                    push_text(&mut parts, &mut text);
+                    section = Some(Vec::new());
+                }
+                ']' => {
+                    let Some(mut section_parts) = section.take() else {
+                        return Err(TemplateError::UnexpectedClose(']'));
+                    };
+
+                    push_text(&mut section_parts, &mut text);
+                    parts.push(Part::Section(section_parts));
+                }
+                '}' => return Err(TemplateError::UnexpectedClose('}')),
+                _ => text.push(char),
+            }
+        }
+
+        if section.is_some() {
+            return Err(TemplateError::UnclosedSection);
+        }
+
+        if !has_message {
+            return Err(TemplateError::MissingMessage);
+        }
+
+        push_text(&mut parts, &mut text);
+        Ok(Self {
+            parts,
+            requires_name,
+        })
+    }
+
+    /// If `{name}` is used outside of a `[...]` section, so the template only makes sense
+    /// when the name is being said.
+    #[must_use]
+    pub fn requires_name(&self) -> bool {
+        self.requires_name
+    }
+
+    #[must_use]
+    pub fn render(&self, values: &TemplateValues<'_>) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Section(section) => {
+                    let is_filled = section.iter().all(|part| match part {
+                        Part::Placeholder(placeholder) => values.get(*placeholder).is_some(),
+                        _ => true,
+                    });
+
+                    if is_filled {
+                        render_parts(&mut out, section, values);
+                    }
+                }
+                part => render_parts(&mut out, std::slice::from_ref(part), values),
+            }
+        }
+
+        out.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+fn push_text(parts: &mut Vec<Part>, text: &mut String) {
+    if !text.is_empty() {
+        parts.push(Part::Text(std::mem::take(text)));
+    }
+}
+
+fn render_parts(out: &mut String, parts: &[Part], values: &TemplateValues<'_>) {
+    for part in parts {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Placeholder(placeholder) => out.push_str(values.get(*placeholder).unwrap_or("")),
+            Part::Section(section) => render_parts(out, section, values),
+        }
+    }
+}
+
+/// Fills the placeholders of a fixed phrase from the translation catalog.
+///
+/// This is a single pass, so placeholders inside of the values are left as they are.
+#[must_use]
+pub fn fill_phrase(phrase: &str, values: &TemplateValues<'_>) -> String {
+    let mut out = String::with_capacity(phrase.len());
+    let mut rest = phrase;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let placeholder = rest
+            .find('}')
+            .and_then(|end| Some((end, Placeholder::from_name(&rest[1..end])?)));
+
+        if let Some((end, placeholder)) = placeholder {
+            out.push_str(values.get(placeholder).unwrap_or(""));
+            rest = &rest[end + 1..];
+        } else {
+            out.push('{');
+            rest = &rest[1..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_phrase, TemplateError, TemplateValues, XsaidTemplate};
+
+    fn parse(source: &str) -> XsaidTemplate {
+        XsaidTemplate::parse(source).expect("template should parse")
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse_err = |source| XsaidTemplate::parse(source).unwrap_err();
+
+        assert!(matches!(
+            parse_err("{name} said"),
+            TemplateError::MissingMessage
+        ));
+        assert!(matches!(
+            parse_err("[{message}]"),
+            TemplateError::MissingMessage
+        ));
+        assert!(matches!(
+            parse_err("{message}[a [b]]"),
+            TemplateError::NestedSection
+        ));
+        assert!(matches!(
+            parse_err("{message}[a"),
+            TemplateError::UnclosedSection
+        ));
+        assert!(matches!(
+            parse_err("{message"),
+            TemplateError::UnclosedPlaceholder
+        ));
+        assert!(matches!(
+            parse_err("{message}]"),
+            TemplateError::UnexpectedClose(']')
+        ));
+        assert!(matches!(
+            parse_err("{message}}"),
+            TemplateError::UnexpectedClose('}')
+        ));
+        assert!(matches!(
+            parse_err("{user}: {message}"),
+            TemplateError::UnknownPlaceholder(name) if name == "user"
+        ));
+        assert!(matches!(
+            parse_err(&"{message}".repeat(30)),
+            TemplateError::TooLong
+        ));
+    }
+
+    #[test]
+    fn requires_name() {
+        assert!(parse("{name}: {message}").requires_name());
+        assert!(!parse("[{name}: ]{message}").requires_name());
+        assert!(!parse("{message}").requires_name());
+    }
+
+    #[test]
+    fn render() {
+        let template = parse("{name}[ replying to {reply}] said {message}[ with {link}]");
+        let values = TemplateValues {
+            name: Some("Alice"),
+            message: Some("hello"),
+            reply: Some("Bob"),
+            ..Default::default()
+        };
+
+        assert_eq!(template.render(&values), "Alice replying to Bob said hello");
+
+        let values = TemplateValues {
+            name: Some("Alice"),
+            message: Some("hello"),
+            reply: Some("  "),
+            link: Some("a link"),
+            ..Default::default()
+        };
+
+        assert_eq!(template.render(&values), "Alice said hello with a link");
+    }
+
+    #[test]
+    fn fill_phrase_single_pass() {
+        let values = TemplateValues {
+            name: Some("{message}"),
+            message: Some("hi {name}"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            fill_phrase("{name} said: {message}", &values),
+            "{message} said: hi {name}"
+        );
+        assert_eq!(fill_phrase("{unknown} {name", &values), "{unknown} {name");
+        assert_eq!(fill_phrase("{link}!", &values), "!");
+    }
+}
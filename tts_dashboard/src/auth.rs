@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use axum::http::{header::AUTHORIZATION, HeaderMap};
+use serenity::all as serenity;
+use sha2::Digest as _;
+
+use crate::{error::ApiError, ApiState};
+
+const CURRENT_AUTHORIZATION_URL: &str = "https://discord.com/api/v10/oauth2/@me";
+const USER_GUILDS_URL: &str = "https://discord.com/api/v10/users/@me/guilds";
+
+/// The guilds of each bearer token, keyed by the token's SHA-256 hash and cached to avoid
+/// hitting Discord's rate limits.
+pub type SessionCache = mini_moka::sync::Cache<[u8; 32], Arc<[UserGuild]>>;
+
+/// Discord's `/oauth2/@me`, used to check which application a token was issued to.
+#[derive(serde::Deserialize)]
+struct CurrentAuthorization {
+    application: AuthorizedApplication,
+}
+
+#[derive(serde::Deserialize)]
+struct AuthorizedApplication {
+    id: serenity::ApplicationId,
+}
+
+/// A guild from Discord's `/users/@me/guilds`, with the user's permissions in it.
+#[derive(Clone, Copy, serde::Deserialize)]
+pub struct UserGuild {
+    id: serenity::GuildId,
+    permissions: serenity::Permissions,
+}
+
+/// Rejects tokens issued to other applications, which could otherwise be replayed here.
+async fn check_token_application(
+    reqwest: &reqwest::Client,
+    token: &str,
+    application_id: serenity::ApplicationId,
+) -> Result<(), ApiError> {
+    let response = reqwest
+        .get(CURRENT_AUTHORIZATION_URL)
+        .bearer_auth(token)
+        .send()
+        .await?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let authorization: CurrentAuthorization = response.error_for_status()?.json().await?;
+    if authorization.application.id == application_id {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+async fn fetch_user_guilds(
+    reqwest: &reqwest::Client,
+    token: &str,
+) -> Result<Arc<[UserGuild]>, ApiError> {
+    let response = reqwest
+        .get(USER_GUILDS_URL)
+        .bearer_auth(token)
+        .send()
+        .await?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let guilds: Vec<UserGuild> = response.error_for_status()?.json().await?;
+    Ok(guilds.into())
+}
+
+/// Checks that the request's bearer token belongs to a user who can manage the guild.
+pub async fn require_manage_guild(
+    state: &ApiState,
+    headers: &HeaderMap,
+    guild_id: serenity::GuildId,
+) -> Result<(), ApiError> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+
+    let token_hash: [u8; 32] = sha2::Sha256::digest(token).into();
+    let guilds = if let Some(guilds) = state.sessions.get(&token_hash) {
+        guilds
+    } else {
+        // Bot accounts share their ID with the application they belong to.
+        let application_id = serenity::ApplicationId::new(state.cache.current_user().id.get());
+        check_token_application(&state.data.reqwest, token, application_id).await?;
+
+        let guilds = fetch_user_guilds(&state.data.reqwest, token).await?;
+        state.sessions.insert(token_hash, guilds.clone());
+        guilds
+    };
+
+    let guild = guilds
+        .iter()
+        .find(|guild| guild.id == guild_id)
+        .ok_or(ApiError::Forbidden)?;
+
+    if guild.permissions.manage_guild() || guild.permissions.administrator() {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden)
+    }
+}
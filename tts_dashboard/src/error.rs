@@ -0,0 +1,44 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+#[derive(serde::Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+}
+
+pub enum ApiError {
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    BadRequest(&'static str),
+    Internal(anyhow::Error),
+}
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            Self::Unauthorized => (StatusCode::UNAUTHORIZED, "Invalid or missing bearer token"),
+            Self::Forbidden => (
+                StatusCode::FORBIDDEN,
+                "You need the Manage Server permission to change these settings",
+            ),
+            Self::NotFound => (StatusCode::NOT_FOUND, "The bot is not in this server"),
+            Self::BadRequest(error) => (StatusCode::BAD_REQUEST, error),
+            Self::Internal(err) => {
+                tracing::error!("Dashboard API error: {err:?}");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+        };
+
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
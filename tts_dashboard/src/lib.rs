@@ -0,0 +1,51 @@
+#![feature(let_chains)]
+
+mod auth;
+mod error;
+mod settings;
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{routing::get, Router};
+use serenity::all as serenity;
+
+use tts_core::structs::{DashboardConfig, Data, Result};
+
+#[derive(Clone)]
+struct ApiState {
+    data: Arc<Data>,
+    cache: Arc<serenity::Cache>,
+    sessions: auth::SessionCache,
+}
+
+/// Serves the guild settings API used by the web dashboard.
+///
+/// Requests are authenticated with a Discord OAuth2 bearer token issued to this bot's
+/// application, for a user with the `MANAGE_GUILD` permission in the guild being changed.
+pub async fn serve(
+    data: Arc<Data>,
+    cache: Arc<serenity::Cache>,
+    config: DashboardConfig,
+) -> Result<()> {
+    let state = ApiState {
+        data,
+        cache,
+        sessions: mini_moka::sync::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(60 * 5))
+            .build(),
+    };
+
+    let router = Router::new()
+        .route(
+            "/guilds/{guild_id}/settings",
+            get(settings::get_settings).patch(settings::patch_settings),
+        )
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(config.bind_address).await?;
+    tracing::info!("Dashboard API listening on {}", config.bind_address);
+
+    axum::serve(listener, router).await?;
+    Ok(())
+}
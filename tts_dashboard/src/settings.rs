@@ -0,0 +1,398 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Deserializer};
+use serenity::all as serenity;
+
+use tts_core::{
+    constants::MAX_XSAID_WINDOW,
+    database_models::{GuildColumn, VoiceAnnouncementColumn},
+    structs::{LinkReadingMode, NameSource, StageReadingMode},
+    xsaid_template::XsaidTemplate,
+};
+
+use crate::{auth::require_manage_guild, error::ApiError, ApiState};
+
+/// Allows telling apart a missing field, which is left alone, from `null`, which resets the setting.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+fn check_prefix(prefix: &str) -> Result<(), ApiError> {
+    if prefix.is_empty() || prefix.len() > 5 || prefix.matches(' ').count() > 1 {
+        Err(ApiError::BadRequest(
+            "Prefixes must be 5 or less characters with maximum 1 space",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The settings shown in `/settings` which apply to the whole server.
+#[derive(serde::Serialize)]
+pub struct GuildSettings {
+    channel: Option<serenity::ChannelId>,
+    required_role: Option<serenity::RoleId>,
+    prefix: String,
+    required_prefix: Option<String>,
+    language: Option<String>,
+    auto_join: bool,
+    idle_timeout: u16,
+    idle_leave_message: bool,
+    xsaid: bool,
+    xsaid_window: u16,
+    xsaid_reannounce: bool,
+    xsaid_template: Option<String>,
+    bot_ignore: bool,
+    audience_ignore: bool,
+    require_voice: bool,
+    text_in_voice: bool,
+    skip_emoji: bool,
+    describe_messages: bool,
+    read_edits: bool,
+    read_threads: bool,
+    stage_auto_speak: bool,
+    announce_speakers: bool,
+    announce_joins: bool,
+    announce_leaves: bool,
+    link_reading: LinkReadingMode,
+    stage_reading: StageReadingMode,
+    name_source: NameSource,
+    msg_length: u16,
+    repeated_chars: u8,
+
+    // These depend on premium, so can only be changed with commands.
+    voice_mode: &'static str,
+    default_voice: String,
+    to_translate: bool,
+    target_lang: Option<String>,
+}
+
+/// Changes to [`GuildSettings`], where missing fields are left unchanged.
+#[derive(Deserialize)]
+pub struct SettingsPatch {
+    #[serde(default, deserialize_with = "nullable")]
+    channel: Option<Option<serenity::ChannelId>>,
+    #[serde(default, deserialize_with = "nullable")]
+    required_role: Option<Option<serenity::RoleId>>,
+    prefix: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    required_prefix: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    language: Option<Option<String>>,
+    auto_join: Option<bool>,
+    idle_timeout: Option<u16>,
+    idle_leave_message: Option<bool>,
+    xsaid: Option<bool>,
+    xsaid_window: Option<u16>,
+    xsaid_reannounce: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
+    xsaid_template: Option<Option<String>>,
+    bot_ignore: Option<bool>,
+    audience_ignore: Option<bool>,
+    require_voice: Option<bool>,
+    text_in_voice: Option<bool>,
+    skip_emoji: Option<bool>,
+    describe_messages: Option<bool>,
+    read_edits: Option<bool>,
+    read_threads: Option<bool>,
+    stage_auto_speak: Option<bool>,
+    announce_speakers: Option<bool>,
+    announce_joins: Option<bool>,
+    announce_leaves: Option<bool>,
+    link_reading: Option<LinkReadingMode>,
+    stage_reading: Option<StageReadingMode>,
+    name_source: Option<NameSource>,
+    msg_length: Option<u16>,
+    repeated_chars: Option<u8>,
+}
+
+async fn fetch_settings(
+    state: &ApiState,
+    guild_id: serenity::GuildId,
+) -> Result<GuildSettings, ApiError> {
+    let data = &state.data;
+    let (guild_row, voice_announcements_row) = tokio::try_join!(
+        data.guilds_db.get(guild_id.into()),
+        data.voice_announcements_db.get(guild_id.into()),
+    )?;
+
+    let voice_mode = guild_row.voice_mode;
+    let guild_voice_row = data
+        .guild_voice_db
+        .get((guild_id.into(), voice_mode))
+        .await?;
+
+    let default_voice = if guild_voice_row.guild_id.is_none() {
+        voice_mode.default_voice()
+    } else {
+        guild_voice_row.voice.as_str()
+    };
+
+    Ok(GuildSettings {
+        channel: guild_row.channel,
+        required_role: guild_row.required_role,
+        prefix: guild_row.prefix.to_string(),
+        required_prefix: guild_row.required_prefix.map(|p| p.to_string()),
+        language: guild_row.language.map(|l| l.to_string()),
+        auto_join: guild_row.auto_join(),
+        idle_timeout: guild_row.idle_timeout.map(|t| t.get()).unwrap_or(0),
+        idle_leave_message: guild_row.idle_leave_message(),
+        xsaid: guild_row.xsaid(),
+        xsaid_window: guild_row.xsaid_window,
+        xsaid_reannounce: guild_row.xsaid_reannounce(),
+        xsaid_template: guild_row.xsaid_template.as_deref().map(String::from),
+        bot_ignore: guild_row.bot_ignore(),
+        audience_ignore: guild_row.audience_ignore(),
+        require_voice: guild_row.require_voice(),
+        text_in_voice: guild_row.text_in_voice(),
+        skip_emoji: guild_row.skip_emoji(),
+        describe_messages: guild_row.describe_messages(),
+        read_edits: guild_row.read_edits(),
+        read_threads: guild_row.read_threads(),
+        stage_auto_speak: guild_row.stage_auto_speak(),
+        announce_speakers: guild_row.announce_speakers(),
+        announce_joins: voice_announcements_row.announce_joins,
+        announce_leaves: voice_announcements_row.announce_leaves,
+        link_reading: guild_row.link_reading,
+        stage_reading: guild_row.stage_reading,
+        name_source: guild_row.name_source,
+        msg_length: guild_row.msg_length,
+        repeated_chars: guild_row.repeated_chars.map(|c| c.get()).unwrap_or(0),
+        voice_mode: voice_mode.into(),
+        default_voice: default_voice.to_owned(),
+        to_translate: guild_row.to_translate(),
+        target_lang: guild_row.target_lang.map(|l| l.to_string()),
+    })
+}
+
+/// Checks the parts of a patch which can be invalid, so nothing is written if any are.
+fn validate_patch(
+    state: &ApiState,
+    guild_id: serenity::GuildId,
+    patch: &SettingsPatch,
+) -> Result<(), ApiError> {
+    let guild = state.cache.guild(guild_id).ok_or(ApiError::NotFound)?;
+
+    if let Some(Some(channel_id)) = patch.channel {
+        let is_valid = guild.channels.get(&channel_id).is_some_and(|channel| {
+            matches!(
+                channel.kind,
+                serenity::ChannelType::Text | serenity::ChannelType::Forum
+            )
+        });
+
+        if !is_valid {
+            return Err(ApiError::BadRequest(
+                "The setup channel must be a text or forum channel in this server",
+            ));
+        }
+    }
+
+    if let Some(Some(role_id)) = patch.required_role
+        && !guild.roles.contains_key(&role_id)
+    {
+        return Err(ApiError::BadRequest(
+            "The required role must be a role in this server",
+        ));
+    }
+
+    if let Some(prefix) = &patch.prefix {
+        check_prefix(prefix)?;
+    }
+
+    if let Some(Some(required_prefix)) = &patch.required_prefix {
+        check_prefix(required_prefix)?;
+    }
+
+    if let Some(Some(language)) = &patch.language
+        && !state
+            .data
+            .translations
+            .languages()
+            .any(|(locale, _)| locale == language)
+    {
+        return Err(ApiError::BadRequest("Unsupported language"));
+    }
+
+    if let Some(Some(template)) = &patch.xsaid_template
+        && XsaidTemplate::parse(template).is_err()
+    {
+        return Err(ApiError::BadRequest(
+            "The xsaid template is invalid, check it with `/set xsaid_template`",
+        ));
+    }
+
+    if patch.idle_timeout.is_some_and(|minutes| minutes > 1440) {
+        return Err(ApiError::BadRequest(
+            "The idle timeout cannot be above 1440 minutes",
+        ));
+    }
+
+    if patch
+        .xsaid_window
+        .is_some_and(|seconds| seconds > MAX_XSAID_WINDOW)
+    {
+        return Err(ApiError::BadRequest(
+            "The xsaid window cannot be above 3600 seconds",
+        ));
+    }
+
+    if patch
+        .msg_length
+        .is_some_and(|seconds| !(10..=60).contains(&seconds))
+    {
+        return Err(ApiError::BadRequest(
+            "The max message length must be between 10 and 60 seconds",
+        ));
+    }
+
+    if patch
+        .repeated_chars
+        .is_some_and(|chars| chars != 0 && !(5..=100).contains(&chars))
+    {
+        return Err(ApiError::BadRequest(
+            "The max repeated characters must be 0 or between 5 and 100",
+        ));
+    }
+
+    Ok(())
+}
+
+async fn apply_patch(
+    state: &ApiState,
+    guild_id: serenity::GuildId,
+    patch: SettingsPatch,
+) -> Result<(), ApiError> {
+    let data = &state.data;
+    let mut guild_update = data.guilds_db.set_many(guild_id.into());
+
+    if let Some(channel) = patch.channel {
+        let channel = channel.map(|c| c.get() as i64).unwrap_or(0);
+        guild_update = guild_update.set(GuildColumn::Channel, channel);
+    }
+
+    if let Some(role) = patch.required_role {
+        let role = role.map(|r| r.get() as i64);
+        guild_update = guild_update.set(GuildColumn::RequiredRole, role);
+    }
+
+    if let Some(prefix) = patch.prefix {
+        guild_update = guild_update.set(GuildColumn::Prefix, prefix);
+    }
+
+    if let Some(required_prefix) = patch.required_prefix {
+        guild_update = guild_update.set(GuildColumn::RequiredPrefix, required_prefix);
+    }
+
+    if let Some(language) = patch.language {
+        guild_update = guild_update.set(GuildColumn::Language, language);
+    }
+
+    if let Some(template) = patch.xsaid_template {
+        guild_update = guild_update.set(GuildColumn::XsaidTemplate, template);
+    }
+
+    if let Some(minutes) = patch.idle_timeout {
+        guild_update = guild_update.set(GuildColumn::IdleTimeout, minutes as i16);
+    }
+
+    if let Some(mode) = patch.link_reading {
+        guild_update = guild_update.set(GuildColumn::LinkReading, mode as i16);
+    }
+
+    if let Some(mode) = patch.stage_reading {
+        guild_update = guild_update.set(GuildColumn::StageReading, mode as i16);
+    }
+
+    if let Some(source) = patch.name_source {
+        guild_update = guild_update.set(GuildColumn::NameSource, source as i16);
+    }
+
+    if let Some(seconds) = patch.xsaid_window {
+        guild_update = guild_update.set(GuildColumn::XsaidWindow, seconds as i16);
+    }
+
+    if let Some(seconds) = patch.msg_length {
+        guild_update = guild_update.set(GuildColumn::MsgLength, seconds as i16);
+    }
+
+    if let Some(chars) = patch.repeated_chars {
+        guild_update = guild_update.set(GuildColumn::RepeatedChars, chars as i16);
+    }
+
+    let guild_bools = [
+        (patch.auto_join, GuildColumn::AutoJoin),
+        (patch.idle_leave_message, GuildColumn::IdleLeaveMessage),
+        (patch.xsaid, GuildColumn::Xsaid),
+        (patch.xsaid_reannounce, GuildColumn::XsaidReannounce),
+        (patch.bot_ignore, GuildColumn::BotIgnore),
+        (patch.audience_ignore, GuildColumn::AudienceIgnore),
+        (patch.require_voice, GuildColumn::RequireVoice),
+        (patch.text_in_voice, GuildColumn::TextInVoice),
+        (patch.skip_emoji, GuildColumn::SkipEmoji),
+        (patch.describe_messages, GuildColumn::DescribeMessages),
+        (patch.read_edits, GuildColumn::ReadEdits),
+        (patch.read_threads, GuildColumn::ReadThreads),
+        (patch.stage_auto_speak, GuildColumn::StageAutoSpeak),
+        (patch.announce_speakers, GuildColumn::AnnounceSpeakers),
+    ];
+
+    for (value, column) in guild_bools {
+        if let Some(value) = value {
+            guild_update = guild_update.set(column, value);
+        }
+    }
+
+    let mut announcements_update = data.voice_announcements_db.set_many(guild_id.into());
+    if let Some(announce_joins) = patch.announce_joins {
+        announcements_update =
+            announcements_update.set(VoiceAnnouncementColumn::AnnounceJoins, announce_joins);
+    }
+
+    if let Some(announce_leaves) = patch.announce_leaves {
+        announcements_update =
+            announcements_update.set(VoiceAnnouncementColumn::AnnounceLeaves, announce_leaves);
+    }
+
+    guild_update.execute().await?;
+    if patch.announce_joins.is_some() || patch.announce_leaves.is_some() {
+        // The announcements row references the guild row, which may not exist yet.
+        data.guilds_db.create_row(guild_id.into()).await?;
+    }
+
+    announcements_update.execute().await?;
+    Ok(())
+}
+
+pub async fn get_settings(
+    State(state): State<ApiState>,
+    Path(guild_id): Path<serenity::GuildId>,
+    headers: HeaderMap,
+) -> Result<Json<GuildSettings>, ApiError> {
+    require_manage_guild(&state, &headers, guild_id).await?;
+    if state.cache.guild(guild_id).is_none() {
+        return Err(ApiError::NotFound);
+    }
+
+    fetch_settings(&state, guild_id).await.map(Json)
+}
+
+pub async fn patch_settings(
+    State(state): State<ApiState>,
+    Path(guild_id): Path<serenity::GuildId>,
+    headers: HeaderMap,
+    Json(patch): Json<SettingsPatch>,
+) -> Result<Json<GuildSettings>, ApiError> {
+    require_manage_guild(&state, &headers, guild_id).await?;
+    validate_patch(&state, guild_id, &patch)?;
+    apply_patch(&state, guild_id, patch).await?;
+
+    fetch_settings(&state, guild_id).await.map(Json)
+}
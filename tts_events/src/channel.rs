@@ -25,8 +25,21 @@ pub async fn channel_delete(
     if call_channel_id == Some(channel.id) {
         // Ignore errors from leaving the channel, probably already left.
         let _ = data.songbird.remove(channel.guild_id).await;
-        data.last_to_xsaid_tracker.remove(&channel.guild_id);
+        data.last_to_xsaid_tracker.remove_guild(channel.guild_id);
+        data.queued_messages.remove_guild(channel.guild_id);
     }
 
     Ok(())
 }
+
+/// Stops reading messages from threads once they are archived or deleted.
+pub fn thread_closed(
+    framework_ctx: FrameworkContext<'_>,
+    guild_id: serenity::GuildId,
+    thread_id: serenity::ChannelId,
+) {
+    let data = framework_ctx.user_data();
+    for queued_message in data.queued_messages.remove_channel(guild_id, thread_id) {
+        queued_message.stop();
+    }
+}
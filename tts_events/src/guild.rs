@@ -1,4 +1,3 @@
-use aformat::aformat;
 use reqwest::StatusCode;
 use tracing::info;
 
@@ -22,23 +21,18 @@ pub async fn guild_create(
     };
 
     let data = framework_ctx.user_data();
-    let title = aformat!("Welcome to {}!", &ctx.cache.current_user().name);
-    let embeds = [CreateEmbed::default()
-        .title(title.as_str())
-        .description(format!("
-Hello! Someone invited me to your server `{}`!
-TTS Bot is a text to speech bot, as in, it reads messages from a text channel and speaks it into a voice channel
-
-**Most commands need to be done on your server, such as `/setup` and `/join`**
+    let language = data
+        .locale(None, Some(guild.id), Some(&*guild.preferred_locale))
+        .await?;
 
-I need someone with the administrator permission to do `/setup #channel`
-You can then do `/join` in that channel and I will join your voice channel!
-Then, you can just type normal messages and I will say them, like magic!
-
-You can view all the commands with `/help`
-Ask questions by either responding here or asking on the support server!",
-        guild.name))
-        .footer(CreateEmbedFooter::new(format!("Support Server: {} | Bot Invite: https://bit.ly/TTSBotSlash", data.config.main_server_invite)))
+    let t = |key: &'static str| data.translations.get(language, key);
+    let title = t("guild_welcome.title").replace("{bot_name}", &ctx.cache.current_user().name);
+    let embeds = [CreateEmbed::default()
+        .title(title)
+        .description(t("guild_welcome.description").replace("{guild_name}", &guild.name))
+        .footer(CreateEmbedFooter::new(
+            t("guild_welcome.footer").replace("{server_invite}", &data.config.main_server_invite),
+        ))
         .author(CreateEmbedAuthor::new(&owner_tag).icon_url(owner_face))];
 
     match guild
@@ -35,6 +35,23 @@ pub fn get_intents() -> serenity::GatewayIntents {
 pub async fn listen(framework_ctx: FrameworkContext<'_>, event: &Event) -> Result<()> {
     match event {
         Event::Message { new_message } => message(framework_ctx, new_message).await,
+        Event::MessageUpdate { event, .. } => message_update(framework_ctx, &event.message).await,
+        Event::MessageDelete {
+            guild_id,
+            deleted_message_id,
+            ..
+        } => {
+            message_delete(framework_ctx, *guild_id, &[*deleted_message_id]);
+            Ok(())
+        }
+        Event::MessageDeleteBulk {
+            guild_id,
+            multiple_deleted_messages_ids,
+            ..
+        } => {
+            message_delete(framework_ctx, *guild_id, multiple_deleted_messages_ids);
+            Ok(())
+        }
         Event::GuildCreate { guild, is_new } => guild_create(framework_ctx, guild, *is_new).await,
         Event::Ready { data_about_bot } => ready(framework_ctx, data_about_bot).await,
         Event::GuildDelete { incomplete, full } => {
@@ -50,6 +67,20 @@ pub async fn listen(framework_ctx: FrameworkContext<'_>, event: &Event) -> Resul
             voice_state_update(framework_ctx, old.as_ref(), new).await
         }
         Event::ChannelDelete { channel, .. } => channel_delete(framework_ctx, channel).await,
+        Event::ThreadUpdate { new, .. } => {
+            if new
+                .thread_metadata
+                .is_some_and(|metadata| metadata.archived())
+            {
+                thread_closed(framework_ctx, new.guild_id, new.id);
+            }
+
+            Ok(())
+        }
+        Event::ThreadDelete { thread, .. } => {
+            thread_closed(framework_ctx, thread.guild_id, thread.id);
+            Ok(())
+        }
         Event::InteractionCreate { interaction } => {
             interaction_create(framework_ctx, interaction).await
         }
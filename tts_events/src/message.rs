@@ -8,12 +8,12 @@ use poise::serenity_prelude as serenity;
 
 use tts_core::{
     common::{dm_generic, random_footer},
-    constants::DM_WELCOME_MESSAGE,
+    database_models::UserColumn,
     opt_ext::OptionTryUnwrap,
     structs::{Data, FrameworkContext, Result},
 };
 
-use tts::process_tts_msg;
+use tts::{drop_queued_msg, process_tts_edit, process_tts_msg};
 
 mod tts;
 
@@ -30,6 +30,27 @@ pub async fn message(
     Ok(())
 }
 
+pub async fn message_update(
+    framework_ctx: FrameworkContext<'_>,
+    message: &serenity::Message,
+) -> Result<()> {
+    process_tts_edit(framework_ctx, message).await
+}
+
+pub fn message_delete(
+    framework_ctx: FrameworkContext<'_>,
+    guild_id: Option<serenity::GuildId>,
+    message_ids: &[serenity::MessageId],
+) {
+    let Some(guild_id) = guild_id else {
+        return;
+    };
+
+    for message_id in message_ids {
+        drop_queued_msg(framework_ctx, guild_id, *message_id);
+    }
+}
+
 async fn process_mention_msg(
     framework_ctx: FrameworkContext<'_>,
     message: &serenity::Message,
@@ -172,18 +193,23 @@ async fn process_support_dm(
                 .await?;
         }
     } else {
+        let language = data.locale(Some(message.author.id), None, None).await?;
+        let t = |key: &'static str| data.translations.get(language, key);
+
         let (client_id, title) = {
             let current_user = ctx.cache.current_user();
             (
                 current_user.id,
-                aformat!("Welcome to {} Support DMs!", &current_user.name),
+                t("dm.welcome_title").replace("{bot_name}", &current_user.name),
             )
         };
 
         let embeds = [CreateEmbed::default()
-            .title(title.as_str())
-            .description(DM_WELCOME_MESSAGE)
+            .title(title)
+            .description(t("dm.welcome"))
             .footer(CreateEmbedFooter::new(random_footer(
+                &data.translations,
+                language,
                 &data.config.main_server_invite,
                 client_id,
             )))];
@@ -193,7 +219,7 @@ async fn process_support_dm(
             .await?;
 
         data.userinfo_db
-            .set_one(message.author.id.into(), "dm_welcomed", &true)
+            .set_one(message.author.id.into(), UserColumn::DmWelcomed, &true)
             .await?;
         if channel_id.pins(&ctx.http).await?.len() < 50 {
             welcome_msg.pin(&ctx.http, None).await?;
@@ -1,17 +1,48 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, sync::Arc};
 
 use aformat::ToArrayString as _;
 use poise::serenity_prelude as serenity;
 
 use tts_core::{
-    common::{clean_msg, fetch_audio, prepare_url},
+    common::{
+        clean_msg, fetch_input, is_setup_channel, is_stage_moderator, prepare_url,
+        request_stage_speak, split_sentences, thread_parent, Phrases,
+    },
+    constants::{DEFAULT_MESSAGE_LENGTH_LIMIT, TTS_CHUNK_LENGTH},
     database::{GuildRow, UserRow},
     errors,
     opt_ext::OptionTryUnwrap as _,
-    structs::{FrameworkContext, IsPremium, JoinVCToken, Result, TTSMode},
+    structs::{
+        Data, FrameworkContext, GuildPremium, JoinVCToken, LinkReadingMode, QueuedMessage, Result,
+        StageReadingMode, TTSMode,
+    },
     traits::SongbirdManagerExt as _,
+    xsaid_template::XsaidTemplate,
 };
 
+/// Tells a guild once per outage that their mode is being substituted.
+async fn notify_fallback(
+    http: &serenity::Http,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    mode: TTSMode,
+    fallback: TTSMode,
+) -> Result<()> {
+    let language = data.locale(None, Some(guild_id), None).await?;
+    let msg = data
+        .translations
+        .get(language, "fallback.notice")
+        .replace("{mode}", mode.into())
+        .replace("{fallback}", fallback.into());
+
+    if let Err(err) = channel_id.say(http, msg).await {
+        tracing::warn!("Failed to send fallback notice for {mode}: {err}");
+    }
+
+    Ok(())
+}
+
 pub(crate) async fn process_tts_msg(
     framework_ctx: FrameworkContext<'_>,
     message: &serenity::Message,
@@ -32,7 +63,15 @@ pub(crate) async fn process_tts_msg(
         return Ok(());
     };
 
-    let is_premium = data.is_premium_simple(&ctx.http, guild_id).await?;
+    let premium = data.guild_premium(&ctx.http, guild_id).await?;
+    let length_limit = premium
+        .map(GuildPremium::message_length_limit)
+        .unwrap_or(DEFAULT_MESSAGE_LENGTH_LIMIT);
+
+    if content.len() >= length_limit {
+        return Ok(());
+    }
+
     let (voice, mode) = {
         if let Some(channel_id) = to_autojoin {
             let join_vc_lock = JoinVCToken::acquire(&data, guild_id);
@@ -41,6 +80,8 @@ pub(crate) async fn process_tts_msg(
                 Err(songbird::error::JoinError::TimedOut) => return Ok(()),
                 Err(err) => return Err(err.into()),
             };
+
+            request_stage_speak(&ctx.http, &ctx.cache, &guild_row, guild_id, channel_id).await;
         }
 
         let is_ephemeral = message
@@ -57,28 +98,58 @@ pub(crate) async fn process_tts_msg(
             None => None,
         };
 
-        let (voice, mode) = data
-            .parse_user_or_guild_with_premium(message.author.id, Some((guild_id, is_premium)))
+        let (mut voice, mut mode) = data
+            .parse_user_or_guild_with_premium(message.author.id, Some((guild_id, premium)))
             .await?;
 
+        if let Some(fallback) = data.fallback_mode(mode, premium) {
+            if data.circuit_breakers.should_notify(mode, guild_id) {
+                let channel_id = message.channel_id;
+                notify_fallback(&ctx.http, &data, guild_id, channel_id, mode, fallback).await?;
+            }
+
+            voice = Cow::Borrowed(fallback.default_voice());
+            mode = fallback;
+        }
+
         let nickname_row = data
             .nickname_db
             .get([guild_id.into(), message.author.id.into()])
             .await?;
 
+        let link_names = if guild_row.link_reading == LinkReadingMode::KnownSites {
+            data.link_names(guild_id).await?
+        } else {
+            Arc::default()
+        };
+
+        // Templates are validated when set, so this only fails if the template syntax has changed since.
+        let xsaid_template = guild_row
+            .xsaid_template
+            .as_deref()
+            .and_then(|template| XsaidTemplate::parse(template).ok());
+        let target_lang = data.target_lang(&guild_row, premium);
+
         content = clean_msg(
             &content,
-            &message.author,
+            message,
             &ctx.cache,
             guild_id,
             member_nick,
-            &message.attachments,
-            &voice,
+            data.normalizer(&voice, mode),
+            guild_row.link_reading,
+            &link_names,
             guild_row.xsaid(),
+            guild_row.xsaid_window,
+            guild_row.xsaid_reannounce(),
             guild_row.skip_emoji(),
+            guild_row.describe_messages(),
             guild_row.repeated_chars,
             nickname_row.name.as_deref(),
+            guild_row.name_source,
             user_row.use_new_formatting(),
+            xsaid_template.as_ref(),
+            Phrases::new(&data.translations, target_lang),
             &data.regex_cache,
             &data.last_to_xsaid_tracker,
         );
@@ -93,16 +164,31 @@ pub(crate) async fn process_tts_msg(
         return Ok(());
     }
 
+    // Synthesize each chunk concurrently, so long messages don't wait on a single large request.
     let speaking_rate = data.speaking_rate(message.author.id, mode).await?;
-    let url = prepare_url(
-        data.config.tts_service.clone(),
-        &content,
-        &voice,
-        mode,
-        &speaking_rate,
-        &guild_row.msg_length.to_arraystring(),
-        guild_row.target_lang(IsPremium::from(is_premium)),
-    );
+    let fetches: Vec<_> = split_sentences(&content, TTS_CHUNK_LENGTH)
+        .into_iter()
+        .map(|chunk| {
+            // Each chunk gets its share of the time limit, so it still applies to the whole message.
+            let max_length = (u32::from(guild_row.msg_length) * chunk.len() as u32)
+                .div_ceil(content.len() as u32)
+                .max(1);
+
+            let url = prepare_url(
+                data.config.tts_service.clone(),
+                chunk,
+                &voice,
+                mode,
+                &speaking_rate,
+                &max_length.to_arraystring(),
+                data.target_lang(&guild_row, premium),
+            );
+
+            let data = data.clone();
+            let (chunk, voice) = (chunk.to_owned(), voice.clone());
+            tokio::spawn(async move { fetch_input(&data, url, mode, &chunk, &voice).await })
+        })
+        .collect();
 
     let call_lock = if let Some(call) = data.songbird.get(guild_id) {
         call
@@ -126,32 +212,47 @@ pub(crate) async fn process_tts_msg(
         }
     };
 
-    // Pre-fetch the audio to handle max_length errors
-    let tts_auth_key = data.config.tts_service_auth_key.as_deref();
-    let Some(audio) = fetch_audio(&data.reqwest, url.clone(), tts_auth_key).await? else {
-        return Ok(());
-    };
+    // Pre-fetch the audio to handle max_length errors, stopping at the first chunk that is too
+    // long as skipping it would read the message with a gap in the middle.
+    let mut inputs = Vec::with_capacity(fetches.len());
+    let mut fetches = fetches.into_iter();
+    for fetch in fetches.by_ref() {
+        let Some(input) = fetch.await?? else {
+            break;
+        };
 
-    let hint = audio
-        .headers()
-        .get(reqwest::header::CONTENT_TYPE)
-        .map(|ct| {
-            let mut hint = songbird::input::core::probe::Hint::new();
-            hint.mime_type(ct.to_str()?);
-            Ok::<_, anyhow::Error>(hint)
-        })
-        .transpose()?;
+        inputs.push(input);
+    }
 
-    let input = Box::new(std::io::Cursor::new(audio.bytes().await?));
-    let wrapped_audio =
-        songbird::input::LiveInput::Raw(songbird::input::AudioStream { input, hint });
+    fetches.for_each(|fetch| fetch.abort());
 
-    let track_handle = {
+    if inputs.is_empty() {
+        return Ok(());
+    }
+
+    // Enqueued under one lock so chunks of different messages can't interleave.
+    let tracks: Arc<[_]> = {
         let mut call = call_lock.lock().await;
-        call.enqueue_input(songbird::input::Input::Live(wrapped_audio, None))
-            .await
+        let mut tracks = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            tracks.push(call.enqueue_input(input).await);
+        }
+
+        tracks.into()
     };
 
+    data.last_voice_activity
+        .insert(guild_id, std::time::Instant::now());
+
+    let queued_message = QueuedMessage {
+        channel_id: message.channel_id,
+        tracks: tracks.clone(),
+        content: message.content.clone(),
+        queued_at: std::time::Instant::now(),
+    };
+    data.queued_messages
+        .insert(guild_id, message.id, queued_message);
+
     data.analytics.log(
         Cow::Borrowed(match mode {
             TTSMode::gTTS => "gTTS_tts",
@@ -182,15 +283,57 @@ pub(crate) async fn process_tts_msg(
     let author_name = message.author.name.clone();
     let icon_url = message.author.face();
 
-    errors::handle_track(
-        ctx.clone(),
-        shard_manager,
-        extra_fields,
-        author_name,
-        icon_url,
-        &track_handle,
-    )
-    .map_err(Into::into)
+    for track in tracks.iter() {
+        errors::handle_track(
+            ctx.clone(),
+            shard_manager.clone(),
+            extra_fields.clone(),
+            author_name.clone(),
+            icon_url.clone(),
+            track,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Drops a message's track if it has not finished being read, returning if it was dropped.
+pub(crate) fn drop_queued_msg(
+    framework_ctx: FrameworkContext<'_>,
+    guild_id: serenity::GuildId,
+    message_id: serenity::MessageId,
+) -> bool {
+    let data = framework_ctx.user_data();
+    data.queued_messages
+        .remove(guild_id, message_id)
+        .is_some_and(|queued_message| queued_message.stop())
+}
+
+pub(crate) async fn process_tts_edit(
+    framework_ctx: FrameworkContext<'_>,
+    message: &serenity::Message,
+) -> Result<()> {
+    let data = framework_ctx.user_data();
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+
+    // Discord also sends updates when link embeds load, which shouldn't stop the message.
+    let content_changed = data
+        .queued_messages
+        .get(guild_id, message.id)
+        .is_some_and(|queued_message| queued_message.content != message.content);
+
+    if !content_changed || !drop_queued_msg(framework_ctx, guild_id, message.id) {
+        return Ok(());
+    }
+
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    if guild_row.read_edits() {
+        process_tts_msg(framework_ctx, message).await?;
+    }
+
+    Ok(())
 }
 
 fn run_checks(
@@ -207,7 +350,7 @@ fn run_checks(
         return Ok(None);
     };
 
-    if guild_row.channel != Some(message.channel_id) {
+    if !is_setup_channel(&guild, guild_row, message.channel_id) {
         // "Text in Voice" works by just sending messages in voice channels, so checking for it just takes
         // checking if the message's channel_id is the author's voice channel_id
         if !guild_row.text_in_voice() {
@@ -228,7 +371,9 @@ fn run_checks(
         && let Some(message_member) = &message.member
         && !message_member.roles.contains(&required_role)
     {
-        let Some(channel) = guild.channels.get(&message.channel_id) else {
+        // Threads don't have their own permissions, so check the channel they are in.
+        let channel_id = thread_parent(&guild, message.channel_id).unwrap_or(message.channel_id);
+        let Some(channel) = guild.channels.get(&channel_id) else {
             return Ok(None);
         };
 
@@ -249,10 +394,6 @@ fn run_checks(
         &message.mentions,
     );
 
-    if content.len() >= 1500 {
-        return Ok(None);
-    }
-
     content = content.to_lowercase();
 
     if let Some(required_prefix) = &guild_row.required_prefix {
@@ -297,11 +438,21 @@ fn run_checks(
             let voice_channel = voice_state.unwrap().channel_id.try_unwrap()?;
             let channel = guild.channels.get(&voice_channel).try_unwrap()?;
 
-            if channel.kind == serenity::ChannelType::Stage
-                && voice_state.is_some_and(serenity::VoiceState::suppress)
-                && guild_row.audience_ignore()
-            {
-                return Ok(None); // Is audience
+            if channel.kind == serenity::ChannelType::Stage {
+                let is_audience = voice_state.is_some_and(serenity::VoiceState::suppress);
+                let can_read = match guild_row.stage_reading {
+                    StageReadingMode::Everyone => !(is_audience && guild_row.audience_ignore()),
+                    StageReadingMode::Speakers => !is_audience,
+                    StageReadingMode::Moderators => message.member.as_deref().is_some_and(|m| {
+                        let permissions =
+                            guild.partial_member_permissions_in(channel, message.author.id, m);
+                        is_stage_moderator(permissions)
+                    }),
+                };
+
+                if !can_read {
+                    return Ok(None); // Is audience, or not a moderator
+                }
             }
         }
     }
@@ -103,6 +103,23 @@ fn finalize_startup(ctx: &serenity::Context, data: &Data) {
         tokio::spawn(web_updater.start());
     }
 
+    let announcer = tts_tasks::announcements::Announcer {
+        data: ctx.data(),
+        http: ctx.http.clone(),
+    };
+
+    tokio::spawn(announcer.start());
+
+    let inactivity_checker = tts_tasks::inactivity::InactivityChecker {
+        data: ctx.data(),
+        http: ctx.http.clone(),
+    };
+
+    tokio::spawn(inactivity_checker.start());
+
+    let voice_reloader = tts_tasks::voice_reloader::VoiceReloader { data: ctx.data() };
+    tokio::spawn(voice_reloader.start());
+
     // Tell glibc to let go of the memory it's holding onto.
     // We are very unlikely to reach the peak of memory allocation that was just hit.
     clear_allocator_cache();
@@ -1,8 +1,9 @@
 use poise::serenity_prelude as serenity;
 
 use tts_core::{
+    common::{fetch_guild_tts, spoken_name},
     opt_ext::OptionTryUnwrap,
-    structs::{FrameworkContext, Result},
+    structs::{Data, FrameworkContext, Result, VoiceAnnouncementKind},
 };
 
 pub async fn voice_state_update(
@@ -10,9 +11,6 @@ pub async fn voice_state_update(
     old: Option<&serenity::VoiceState>,
     new: &serenity::VoiceState,
 ) -> Result<()> {
-    // User left vc
-    let Some(old) = old else { return Ok(()) };
-
     let data = framework_ctx.user_data();
 
     // Bot is in vc on server
@@ -21,21 +19,32 @@ pub async fn voice_state_update(
         return Ok(());
     }
 
-    // Check if the bot is leaving
     let ctx = framework_ctx.serenity_context;
     let bot_id = ctx.cache.current_user().id;
-    let leave_vc = match &new.member {
-        // songbird does not clean up state on VC disconnections, so we have to do it here
-        Some(member) if member.user.id == bot_id => true,
-        Some(_) => check_is_lonely(ctx, bot_id, guild_id, old)?,
-        None => false,
-    };
 
-    if leave_vc {
-        data.last_to_xsaid_tracker.remove(&guild_id);
-        data.songbird.remove(guild_id).await?;
+    // User left vc
+    if let Some(old) = old {
+        // Check if the bot is leaving
+        let leave_vc = match &new.member {
+            // songbird does not clean up state on VC disconnections, so we have to do it here
+            Some(member) if member.user.id == bot_id => true,
+            Some(_) => check_is_lonely(ctx, bot_id, guild_id, old)?,
+            None => false,
+        };
+
+        if leave_vc {
+            data.last_to_xsaid_tracker.remove_guild(guild_id);
+            data.queued_messages.remove_guild(guild_id);
+            data.songbird.remove(guild_id).await?;
+            return Ok(());
+        }
     }
 
+    tokio::try_join!(
+        announce_voice_change(ctx, &data, bot_id, guild_id, old, new),
+        announce_speaker_change(ctx, &data, bot_id, guild_id, old, new),
+    )?;
+
     Ok(())
 }
 
@@ -67,3 +76,160 @@ fn check_is_lonely(
 
     Ok(true)
 }
+
+/// Says when a member joins, leaves, or moves into the bot's voice channel, if enabled
+async fn announce_voice_change(
+    ctx: &serenity::Context,
+    data: &Data,
+    bot_id: serenity::UserId,
+    guild_id: serenity::GuildId,
+    old: Option<&serenity::VoiceState>,
+    new: &serenity::VoiceState,
+) -> Result<()> {
+    let Some(member) = &new.member else {
+        return Ok(());
+    };
+
+    // Mute and deafen updates keep the same channel
+    let old_channel_id = old.and_then(|vs| vs.channel_id);
+    if member.user.bot() || old_channel_id == new.channel_id {
+        return Ok(());
+    }
+
+    let bot_channel_id = {
+        let guild = ctx.cache.guild(guild_id).try_unwrap()?;
+        guild.voice_states.get(&bot_id).and_then(|vs| vs.channel_id)
+    };
+
+    let Some(bot_channel_id) = bot_channel_id else {
+        return Ok(());
+    };
+
+    let kind = if new.channel_id == Some(bot_channel_id) {
+        if old_channel_id.is_some() {
+            VoiceAnnouncementKind::Move
+        } else {
+            VoiceAnnouncementKind::Join
+        }
+    } else if old_channel_id == Some(bot_channel_id) {
+        VoiceAnnouncementKind::Leave
+    } else {
+        return Ok(());
+    };
+
+    let row = data.voice_announcements_db.get(guild_id.into()).await?;
+    let enabled = match kind {
+        VoiceAnnouncementKind::Join | VoiceAnnouncementKind::Move => row.announce_joins,
+        VoiceAnnouncementKind::Leave => row.announce_leaves,
+    };
+
+    // Members with flaky connections would otherwise be announced over and over
+    let cooldown_key = (guild_id, member.user.id);
+    let cooldowns = &data.voice_announcement_cooldowns;
+    if !enabled || cooldowns.contains_key(&cooldown_key) {
+        return Ok(());
+    }
+
+    cooldowns.insert(cooldown_key, ());
+
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    let nickname_row = data
+        .nickname_db
+        .get([guild_id.into(), member.user.id.into()])
+        .await?;
+
+    let name = spoken_name(
+        &data.regex_cache,
+        guild_row.name_source,
+        nickname_row.name.as_deref(),
+        member.nick.as_deref(),
+        &member.user,
+    );
+
+    let content = kind.message(&row).replace("{name}", &name);
+    let Some(input) = fetch_guild_tts(data, &ctx.http, guild_id, &content).await? else {
+        return Ok(());
+    };
+
+    if let Some(call_lock) = data.songbird.get(guild_id) {
+        call_lock.lock().await.enqueue_input(input).await;
+        data.last_voice_activity
+            .insert(guild_id, std::time::Instant::now());
+    }
+
+    Ok(())
+}
+
+/// Says when a member in the bot's stage channel becomes a speaker or moves back to the audience, if enabled
+async fn announce_speaker_change(
+    ctx: &serenity::Context,
+    data: &Data,
+    bot_id: serenity::UserId,
+    guild_id: serenity::GuildId,
+    old: Option<&serenity::VoiceState>,
+    new: &serenity::VoiceState,
+) -> Result<()> {
+    let (Some(old), Some(member)) = (old, &new.member) else {
+        return Ok(());
+    };
+
+    if member.user.id == bot_id
+        || old.channel_id != new.channel_id
+        || old.suppress() == new.suppress()
+    {
+        return Ok(());
+    }
+
+    let is_bot_stage = {
+        let guild = ctx.cache.guild(guild_id).try_unwrap()?;
+        let bot_channel_id = guild.voice_states.get(&bot_id).and_then(|vs| vs.channel_id);
+
+        bot_channel_id.is_some_and(|bot_channel_id| {
+            new.channel_id == Some(bot_channel_id)
+                && guild
+                    .channels
+                    .get(&bot_channel_id)
+                    .is_some_and(|channel| channel.kind == serenity::ChannelType::Stage)
+        })
+    };
+
+    if !is_bot_stage {
+        return Ok(());
+    }
+
+    let guild_row = data.guilds_db.get(guild_id.into()).await?;
+    if !guild_row.announce_speakers() {
+        return Ok(());
+    }
+
+    let nickname_row = data
+        .nickname_db
+        .get([guild_id.into(), member.user.id.into()])
+        .await?;
+
+    let name = spoken_name(
+        &data.regex_cache,
+        guild_row.name_source,
+        nickname_row.name.as_deref(),
+        member.nick.as_deref(),
+        &member.user,
+    );
+
+    let content = if new.suppress() {
+        format!("{name} moved to the audience")
+    } else {
+        format!("{name} is now a speaker")
+    };
+
+    let Some(input) = fetch_guild_tts(data, &ctx.http, guild_id, &content).await? else {
+        return Ok(());
+    };
+
+    if let Some(call_lock) = data.songbird.get(guild_id) {
+        call_lock.lock().await.enqueue_input(input).await;
+        data.last_voice_activity
+            .insert(guild_id, std::time::Instant::now());
+    }
+
+    Ok(())
+}
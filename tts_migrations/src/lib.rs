@@ -3,13 +3,12 @@ use std::io::Write;
 use sqlx::{Connection as _, Executor, Row};
 
 use tts_core::{
-    constants::DB_SETUP_QUERY,
+    constants::{DB_SETUP_QUERY, SQLITE_DB_SETUP_QUERY},
     opt_ext::OptionTryUnwrap,
-    structs::{Config, PostgresConfig, Result, TTSMode},
+    storage::{Backend, Dialect, Pool, StorageBackend, Transaction},
+    structs::{Config, Result, TTSMode},
 };
 
-type Transaction<'a> = sqlx::Transaction<'a, sqlx::Postgres>;
-
 async fn migrate_single_to_modes(
     transaction: &mut Transaction<'_>,
     table: &str,
@@ -102,8 +101,37 @@ async fn migrate_speaking_rate_to_mode(transaction: &mut Transaction<'_>) -> Res
     Ok(())
 }
 
+/// Adds a column if it is missing, as SQLite does not support `ADD COLUMN IF NOT EXISTS`.
+async fn add_column(
+    transaction: &mut Transaction<'_>,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let exists_query = match Backend::DIALECT {
+        Dialect::Postgres => {
+            "SELECT 1 FROM information_schema.columns WHERE table_name = $1 AND column_name = $2"
+        }
+        Dialect::Sqlite => "SELECT 1 FROM pragma_table_info($1) WHERE name = $2",
+    };
+
+    let existing_column = transaction
+        .fetch_optional(sqlx::query(exists_query).bind(table).bind(column))
+        .await?;
+
+    if existing_column.is_none() {
+        transaction
+            .execute(&*format!(
+                "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+            ))
+            .await?;
+    }
+
+    Ok(())
+}
+
 // I'll use a proper framework for this one day
-async fn run(config: &mut toml::Table, pool: &sqlx::PgPool) -> Result<()> {
+async fn run(config: &mut toml::Table, pool: &Pool) -> Result<()> {
     let starting_conf = config.clone();
 
     let stolen_config = std::mem::take(config);
@@ -130,7 +158,12 @@ async fn run(config: &mut toml::Table, pool: &sqlx::PgPool) -> Result<()> {
 async fn run_(config: &mut toml::Table, transaction: &mut Transaction<'_>) -> Result<()> {
     let main_config = config["Main"].as_table_mut().try_unwrap()?;
     if main_config.get("setup").is_none() {
-        transaction.execute(DB_SETUP_QUERY).await?;
+        let setup_query = match Backend::DIALECT {
+            Dialect::Postgres => DB_SETUP_QUERY,
+            Dialect::Sqlite => SQLITE_DB_SETUP_QUERY,
+        };
+
+        transaction.execute(setup_query).await?;
         main_config.insert("setup".into(), true.into());
     }
 
@@ -139,6 +172,133 @@ async fn run_(config: &mut toml::Table, transaction: &mut Transaction<'_>) -> Re
         config.insert("Premium-Info".into(), toml::Value::Table(inner));
     }
 
+    if Backend::DIALECT == Dialect::Postgres {
+        run_postgres_legacy(transaction).await?;
+    }
+
+    let id_type = match Backend::DIALECT {
+        Dialect::Postgres => "bigserial",
+        Dialect::Sqlite => "integer",
+    };
+
+    transaction
+        .execute(&*format!(
+            "
+        CREATE TABLE IF NOT EXISTS announcements (
+            id          {id_type}  PRIMARY KEY,
+            guild_id    bigint     NOT NULL,
+            channel_id  bigint,
+            message     text       NOT NULL,
+            schedule    text,
+            next_run    bigint     NOT NULL,
+
+            FOREIGN KEY       (guild_id)
+            REFERENCES guilds (guild_id)
+            ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS announcements_next_run ON announcements (next_run);
+    "
+        ))
+        .await?;
+
+    transaction
+        .execute(
+            "
+        CREATE TABLE IF NOT EXISTS voice_announcements (
+            guild_id         bigint  PRIMARY KEY,
+            announce_joins   bool    DEFAULT False,
+            announce_leaves  bool    DEFAULT False,
+            join_message     text,
+            leave_message    text,
+            move_message     text,
+
+            FOREIGN KEY       (guild_id)
+            REFERENCES guilds (guild_id)
+            ON DELETE CASCADE
+        );
+
+        INSERT INTO voice_announcements(guild_id) VALUES(0) ON CONFLICT (guild_id) DO NOTHING;
+    ",
+        )
+        .await?;
+
+    add_column(transaction, "guilds", "idle_timeout", "smallint DEFAULT 0").await?;
+    add_column(
+        transaction,
+        "guilds",
+        "idle_leave_message",
+        "bool DEFAULT False",
+    )
+    .await?;
+
+    add_column(transaction, "guilds", "language", "varchar(8)").await?;
+    add_column(transaction, "userinfo", "language", "varchar(8)").await?;
+
+    add_column(transaction, "guilds", "link_reading", "smallint DEFAULT 0").await?;
+    transaction
+        .execute(
+            "
+        CREATE TABLE IF NOT EXISTS link_names (
+            guild_id  bigint,
+            domain    varchar(64),
+            name      varchar(32)  NOT NULL,
+
+            PRIMARY KEY (guild_id, domain),
+
+            FOREIGN KEY       (guild_id)
+            REFERENCES guilds (guild_id)
+            ON DELETE CASCADE
+        );
+    ",
+        )
+        .await?;
+
+    add_column(
+        transaction,
+        "guilds",
+        "describe_messages",
+        "bool DEFAULT False",
+    )
+    .await?;
+
+    add_column(transaction, "guilds", "read_edits", "bool DEFAULT False").await?;
+    add_column(transaction, "guilds", "read_threads", "bool DEFAULT False").await?;
+
+    add_column(transaction, "guilds", "stage_reading", "smallint DEFAULT 0").await?;
+    add_column(
+        transaction,
+        "guilds",
+        "stage_auto_speak",
+        "bool DEFAULT True",
+    )
+    .await?;
+    add_column(
+        transaction,
+        "guilds",
+        "announce_speakers",
+        "bool DEFAULT False",
+    )
+    .await?;
+
+    add_column(transaction, "guilds", "xsaid_template", "text").await?;
+    add_column(transaction, "guilds", "xsaid_window", "smallint DEFAULT 60").await?;
+    add_column(
+        transaction,
+        "guilds",
+        "xsaid_reannounce",
+        "bool DEFAULT True",
+    )
+    .await?;
+
+    add_column(transaction, "guilds", "name_source", "smallint DEFAULT 0").await?;
+    add_column(transaction, "nicknames", "locked", "bool DEFAULT False").await?;
+
+    Ok(())
+}
+
+/// Upgrades databases created before the current schema, which only ever existed on Postgres.
+async fn run_postgres_legacy(transaction: &mut Transaction<'_>) -> Result<()> {
     transaction.execute("
         DO $$ BEGIN
             CREATE type TTSMode AS ENUM (
@@ -222,24 +382,17 @@ async fn run_(config: &mut toml::Table, transaction: &mut Transaction<'_>) -> Re
     Ok(())
 }
 
-pub async fn load_db_and_conf() -> Result<(sqlx::PgPool, Config)> {
+pub async fn load_db_and_conf() -> Result<(Pool, Config)> {
     let mut config_toml: toml::Table = std::fs::read_to_string("config.toml")?.parse()?;
-    let postgres: PostgresConfig = toml::Value::try_into(config_toml["PostgreSQL-Info"].clone())?;
-
-    let pool_config = sqlx::postgres::PgPoolOptions::new();
-    let pool_config = if let Some(max_connections) = postgres.max_connections {
-        pool_config.max_connections(max_connections)
-    } else {
-        pool_config
+    let Some(db_config) = config_toml.get(Backend::CONFIG_SECTION) else {
+        anyhow::bail!(
+            "Missing [{}] section in config.toml",
+            Backend::CONFIG_SECTION
+        );
     };
 
-    let pool_options = sqlx::postgres::PgConnectOptions::new()
-        .host(&postgres.host)
-        .username(&postgres.user)
-        .database(&postgres.database)
-        .password(&postgres.password);
-
-    let pool = pool_config.connect_with(pool_options).await?;
+    let db_config = toml::Value::try_into(db_config.clone())?;
+    let pool = Backend::connect(db_config).await?;
     run(&mut config_toml, &pool).await?;
 
     let config = config_toml.try_into()?;
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use serenity::all as serenity;
+
+use tts_core::{
+    common::{fetch_guild_tts, next_cron_run, parse_cron},
+    database_models::AnnouncementRow,
+    structs::{Data, JoinVCToken, Result},
+    traits::SongbirdManagerExt as _,
+};
+
+pub struct Announcer {
+    pub data: Arc<Data>,
+    pub http: Arc<serenity::Http>,
+}
+
+impl Announcer {
+    async fn announce(&self, announcement: &AnnouncementRow) -> Result<()> {
+        let guild_id = serenity::GuildId::new(announcement.guild_id as u64);
+        let call_lock = if let Some(call) = self.data.songbird.get(guild_id) {
+            call
+        } else if let Some(channel_id) = announcement.channel_id {
+            let channel_id = serenity::ChannelId::new(channel_id as u64);
+            let join_vc_token = JoinVCToken::acquire(&self.data, guild_id);
+            match self.data.songbird.join_vc(join_vc_token, channel_id).await {
+                Ok(call) => call,
+                Err(songbird::error::JoinError::TimedOut) => return Ok(()),
+                Err(err) => return Err(err.into()),
+            }
+        } else {
+            // Not connected and nowhere to join, so nobody would hear it.
+            return Ok(());
+        };
+
+        let message = &announcement.message;
+        let Some(input) = fetch_guild_tts(&self.data, &self.http, guild_id, message).await? else {
+            return Ok(());
+        };
+
+        call_lock.lock().await.enqueue_input(input).await;
+        self.data
+            .last_voice_activity
+            .insert(guild_id, std::time::Instant::now());
+
+        Ok(())
+    }
+}
+
+impl crate::Looper for Announcer {
+    const NAME: &'static str = "Announcer";
+    const MILLIS: u64 = 1000 * 30;
+
+    type Error = anyhow::Error;
+    async fn loop_func(&self) -> Result<()> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+
+        let due: Vec<AnnouncementRow> =
+            sqlx::query_as("SELECT * FROM announcements WHERE next_run <= $1")
+                .bind(now)
+                .fetch_all(&self.data.pool)
+                .await?;
+
+        for announcement in due {
+            // Reschedule before announcing, so a failing announcement is not retried every loop.
+            let next_run = announcement
+                .schedule
+                .as_deref()
+                .and_then(|schedule| parse_cron(schedule).ok())
+                .and_then(|schedule| next_cron_run(&schedule, now));
+
+            if let Some(next_run) = next_run {
+                sqlx::query("UPDATE announcements SET next_run = $1 WHERE id = $2")
+                    .bind(next_run)
+                    .bind(announcement.id)
+                    .execute(&self.data.pool)
+                    .await?;
+            } else {
+                sqlx::query("DELETE FROM announcements WHERE id = $1")
+                    .bind(announcement.id)
+                    .execute(&self.data.pool)
+                    .await?;
+            }
+
+            if let Err(err) = self.announce(&announcement).await {
+                tracing::error!(
+                    "{} Error: Failed to announce {}: {err:?}",
+                    Self::NAME,
+                    announcement.id
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::{sync::Arc, time::Duration};
+
+use serenity::all as serenity;
+
+use tts_core::{
+    common::fetch_guild_tts,
+    structs::{Data, Result},
+};
+
+const LEAVE_MESSAGE: &str = "Leaving due to inactivity";
+const MAX_LEAVE_MESSAGE_WAIT: u32 = 30;
+
+/// Disconnects from voice channels that have had nothing said for the guild's idle timeout.
+pub struct InactivityChecker {
+    pub data: Arc<Data>,
+    pub http: Arc<serenity::Http>,
+}
+
+async fn leave_inactive(
+    data: Arc<Data>,
+    http: Arc<serenity::Http>,
+    guild_id: serenity::GuildId,
+    say_leave_message: bool,
+) -> Result<()> {
+    if say_leave_message
+        && let Some(call_lock) = data.songbird.get(guild_id)
+        && let Some(input) = fetch_guild_tts(&data, &http, guild_id, LEAVE_MESSAGE).await?
+    {
+        call_lock.lock().await.enqueue_input(input).await;
+
+        // Give the message a chance to be heard before disconnecting.
+        for _ in 0..MAX_LEAVE_MESSAGE_WAIT {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            if call_lock.lock().await.queue().is_empty() {
+                break;
+            }
+        }
+    }
+
+    data.last_to_xsaid_tracker.remove_guild(guild_id);
+
+    data.queued_messages.remove_guild(guild_id);
+    data.songbird.remove(guild_id).await?;
+    Ok(())
+}
+
+impl crate::Looper for InactivityChecker {
+    const NAME: &'static str = "InactivityChecker";
+    const MILLIS: u64 = 1000 * 60;
+
+    type Error = anyhow::Error;
+    async fn loop_func(&self) -> Result<()> {
+        let last_activity: Vec<_> = self
+            .data
+            .last_voice_activity
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+
+        for (guild_id, last_active) in last_activity {
+            if self.data.songbird.get(guild_id).is_none() {
+                self.data.last_voice_activity.remove(&guild_id);
+                continue;
+            }
+
+            let guild_row = self.data.guilds_db.get(guild_id.into()).await?;
+            let Some(idle_timeout) = guild_row.idle_timeout else {
+                continue;
+            };
+
+            if last_active.elapsed() < Duration::from_secs(u64::from(idle_timeout.get()) * 60) {
+                continue;
+            }
+
+            // Something may have been said since the activity was collected.
+            let activity_map = &self.data.last_voice_activity;
+            if activity_map
+                .remove_if(&guild_id, |_, last| *last == last_active)
+                .is_none()
+            {
+                continue;
+            }
+
+            let data = self.data.clone();
+            let http = self.http.clone();
+            let say_leave_message = guild_row.idle_leave_message();
+            tokio::spawn(async move {
+                if let Err(err) = leave_inactive(data, http, guild_id, say_leave_message).await {
+                    tracing::error!("{} Error: {err:?}", Self::NAME);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
 #![allow(async_fn_in_trait)]
+#![feature(let_chains)]
 #![feature(never_type)]
 
 mod analytics;
+pub mod announcements;
 pub mod bot_list_updater;
+pub mod inactivity;
 pub mod logging;
+pub mod voice_reloader;
 pub mod web_updater;
 
 pub trait Looper {
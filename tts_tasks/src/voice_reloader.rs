@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use tts_core::structs::{Data, Result};
+
+/// Refetches the voice lists from the tts-service, so new voices are picked up without a restart.
+pub struct VoiceReloader {
+    pub data: Arc<Data>,
+}
+
+impl crate::Looper for VoiceReloader {
+    const NAME: &'static str = "VoiceReloader";
+    const MILLIS: u64 = 1000 * 60 * 60;
+
+    type Error = anyhow::Error;
+    async fn loop_func(&self) -> Result<()> {
+        tts_core::voices::reload(&self.data).await?;
+        Ok(())
+    }
+}
@@ -7,7 +7,10 @@ use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
 
 use serenity::all as serenity;
 
-use tts_core::structs::{Result, TTSMode, WebsiteInfo};
+use tts_core::{
+    storage::{Backend, Dialect, Pool, StorageBackend},
+    structs::{Result, TTSMode, WebsiteInfo},
+};
 
 #[allow(dead_code, clippy::match_same_arms)]
 fn remember_to_update_analytics_query() {
@@ -37,7 +40,7 @@ pub struct Updater {
     pub cache: Arc<serenity::Cache>,
     pub reqwest: reqwest::Client,
     pub config: WebsiteInfo,
-    pub pool: sqlx::PgPool,
+    pub pool: Pool,
 }
 
 impl crate::Looper for Updater {
@@ -56,50 +59,58 @@ impl crate::Looper for Updater {
             guild_id: i64,
         }
 
-        let patreon_members = if let Some(mut patreon_service) = self.patreon_service.clone() {
-            patreon_service.set_path("members");
-            let raw_members: HashMap<i64, serde::de::IgnoredAny> = self
-                .reqwest
-                .get(patreon_service)
-                .send()
-                .await?
-                .error_for_status()?
-                .json()
-                .await?;
-
-            raw_members.into_keys().collect()
-        } else {
-            Vec::new()
+        let patreon_members: Vec<i64> =
+            if let Some(mut patreon_service) = self.patreon_service.clone() {
+                patreon_service.set_path("members");
+                let raw_members: HashMap<i64, serde::de::IgnoredAny> = self
+                    .reqwest
+                    .get(patreon_service)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+
+                raw_members.into_keys().collect()
+            } else {
+                Vec::new()
+            };
+
+        let yesterday = match Backend::DIALECT {
+            Dialect::Postgres => "(CURRENT_DATE - 1)",
+            Dialect::Sqlite => "date('now', '-1 day')",
         };
 
         let (message_count, premium_guild_ids) = {
             let mut db_conn = self.pool.acquire().await?;
-            let message_count = sqlx::query_as::<_, AnalyticsQueryResult>(
+            let message_count = sqlx::query_as::<_, AnalyticsQueryResult>(&format!(
                 "
                 SELECT count FROM analytics
-                WHERE date_collected = (CURRENT_DATE - 1) AND (
+                WHERE date_collected = {yesterday} AND (
                     event = 'gTTS_tts'   OR
                     event = 'eSpeak_tts' OR
                     event = 'gCloud_tts' OR
                     event = 'Polly_tts'
                 )
-            ",
-            )
+            "
+            ))
             .fetch_all(&mut *db_conn)
             .await?
             .into_iter()
             .map(|r| r.count as i64)
             .sum::<i64>();
 
-            let premium_guild_ids = sqlx::query_as::<_, PremiumGuildsQueryResult>(
-                "SELECT guild_id FROM guilds WHERE premium_user = ANY($1)",
-            )
-            .bind(&patreon_members)
-            .fetch_all(&mut *db_conn)
-            .await?
-            .into_iter()
-            .map(|g| g.guild_id)
-            .collect::<HashSet<_>>();
+            let mut premium_guilds_query =
+                sqlx::QueryBuilder::new("SELECT guild_id FROM guilds WHERE premium_user ");
+            Backend::push_any_of(&mut premium_guilds_query, &patreon_members);
+
+            let premium_guild_ids = premium_guilds_query
+                .build_query_as::<PremiumGuildsQueryResult>()
+                .fetch_all(&mut *db_conn)
+                .await?
+                .into_iter()
+                .map(|g| g.guild_id)
+                .collect::<HashSet<_>>();
 
             (message_count, premium_guild_ids)
         };